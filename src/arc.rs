@@ -0,0 +1,126 @@
+use std::f32::consts::PI;
+
+/// Elliptical arc in center parameterization.
+/// See https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arc {
+    center_x: f32,
+    center_y: f32,
+    radius_x: f32,
+    radius_y: f32,
+    x_axis_rotation: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+}
+
+impl Arc {
+    /// Converts an arc given the way path data describes it into center parameterization.
+    /// `x_axis_rotation` is in degrees.
+    /// Returns None when the arc must be drawn as a straight line instead,
+    /// which happens when the endpoints coincide or one of the radii is zero.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_endpoints(start_x: f32, start_y: f32,
+                          radius_x: f32, radius_y: f32,
+                          x_axis_rotation: f32,
+                          large_arc: bool, sweep: bool,
+                          end_x: f32, end_y: f32) -> Option<Arc> {
+        if start_x == end_x && start_y == end_y {
+            return None;
+        }
+        let mut radius_x = radius_x.abs();
+        let mut radius_y = radius_y.abs();
+        if radius_x == 0f32 || radius_y == 0f32 {
+            return None;
+        }
+
+        let phi = x_axis_rotation.to_radians();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+
+        // Step 1: compute (x1', y1')
+        let half_dx = (start_x - end_x) / 2f32;
+        let half_dy = (start_y - end_y) / 2f32;
+        let x1 = cos_phi * half_dx + sin_phi * half_dy;
+        let y1 = -sin_phi * half_dx + cos_phi * half_dy;
+
+        // Out-of-range radii are scaled up until the ellipse exactly fits the endpoints.
+        let lambda = (x1 * x1) / (radius_x * radius_x) + (y1 * y1) / (radius_y * radius_y);
+        if lambda > 1f32 {
+            let scale = lambda.sqrt();
+            radius_x *= scale;
+            radius_y *= scale;
+        }
+
+        // Step 2: compute (cx', cy')
+        let rx2 = radius_x * radius_x;
+        let ry2 = radius_y * radius_y;
+        let numerator = rx2 * ry2 - rx2 * y1 * y1 - ry2 * x1 * x1;
+        let denominator = rx2 * y1 * y1 + ry2 * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0f32).sqrt();
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+        let center_x1 = coefficient * radius_x * y1 / radius_y;
+        let center_y1 = -coefficient * radius_y * x1 / radius_x;
+
+        // Step 3: compute (cx, cy) from (cx', cy')
+        let center_x = cos_phi * center_x1 - sin_phi * center_y1 + (start_x + end_x) / 2f32;
+        let center_y = sin_phi * center_x1 + cos_phi * center_y1 + (start_y + end_y) / 2f32;
+
+        // Step 4: compute the start angle and the sweep angle
+        let start_angle = angle_between((1f32, 0f32),
+                                        ((x1 - center_x1) / radius_x, (y1 - center_y1) / radius_y));
+        let mut sweep_angle = angle_between(((x1 - center_x1) / radius_x, (y1 - center_y1) / radius_y),
+                                            ((-x1 - center_x1) / radius_x, (-y1 - center_y1) / radius_y));
+        if !sweep && sweep_angle > 0f32 {
+            sweep_angle -= 2f32 * PI;
+        } else if sweep && sweep_angle < 0f32 {
+            sweep_angle += 2f32 * PI;
+        }
+
+        Some(Arc {
+            center_x,
+            center_y,
+            radius_x,
+            radius_y,
+            x_axis_rotation: phi,
+            start_angle,
+            sweep_angle,
+        })
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.center_x, self.center_y)
+    }
+
+    /// Radii after out-of-range correction.
+    pub fn radii(&self) -> (f32, f32) {
+        (self.radius_x, self.radius_y)
+    }
+
+    /// Start angle in radians.
+    pub fn start_angle(&self) -> f32 {
+        self.start_angle
+    }
+
+    /// Signed sweep angle in radians, positive when the arc goes in the positive-angle direction.
+    pub fn sweep_angle(&self) -> f32 {
+        self.sweep_angle
+    }
+
+    /// Point of the arc, where `t` goes from 0 (start point) to 1 (end point).
+    pub fn point_at(&self, t: f32) -> (f32, f32) {
+        let angle = self.start_angle + self.sweep_angle * t;
+        let (sin_angle, cos_angle) = angle.sin_cos();
+        let (sin_phi, cos_phi) = self.x_axis_rotation.sin_cos();
+        let x = self.radius_x * cos_angle;
+        let y = self.radius_y * sin_angle;
+        (cos_phi * x - sin_phi * y + self.center_x,
+         sin_phi * x + cos_phi * y + self.center_y)
+    }
+}
+
+fn angle_between(u: (f32, f32), v: (f32, f32)) -> f32 {
+    let dot = u.0 * v.0 + u.1 * v.1;
+    let cross = u.0 * v.1 - u.1 * v.0;
+    cross.atan2(dot)
+}
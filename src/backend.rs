@@ -0,0 +1,52 @@
+use std::thread;
+use std::time::Duration;
+
+use error::Error;
+use mouse;
+
+/// Something that can press, release and move a pointer.
+pub trait PointerBackend {
+    fn press(&mut self) -> Result<(), Error>;
+    fn release(&mut self) -> Result<(), Error>;
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error>;
+    fn position(&mut self) -> Result<(f32, f32), Error>;
+
+    /// Waits before the next event, backends which don't drive a real pointer may only pretend to.
+    fn pause(&mut self, duration: Duration) -> Result<(), Error> {
+        thread::sleep(duration);
+        Ok(())
+    }
+
+    /// Makes sure everything asked for so far has reached the pointer.
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Backend which drives the real pointer by spawning `xdotool`.
+#[derive(Debug, Default)]
+pub struct XdotoolBackend;
+
+impl XdotoolBackend {
+    pub fn new() -> XdotoolBackend {
+        XdotoolBackend
+    }
+}
+
+impl PointerBackend for XdotoolBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        mouse::down()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        mouse::up()
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        mouse::move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        mouse::coords()
+    }
+}
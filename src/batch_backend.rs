@@ -0,0 +1,106 @@
+use std::process::Command;
+use std::time::Duration;
+
+use error::Error;
+use backend::PointerBackend;
+use mouse;
+
+/// Default number of chained commands per `xdotool` process.
+pub const DEFAULT_BATCH_SIZE: usize = 200;
+
+/// Backend which chains pointer events into a single `xdotool` invocation,
+/// e.g. `xdotool mousemove 10 20 mousedown 1 mousemove 30 40 mouseup 1`,
+/// instead of spawning a process per event like `XdotoolBackend` does.
+/// Pauses become chained `sleep` commands so pacing is kept.
+/// Commands are sent once the batch is full, when the position is asked for, on `flush` and on drop.
+pub struct BatchingXdotoolBackend {
+    program: String,
+    batch_size: usize,
+    args: Vec<String>,
+    commands: usize,
+}
+
+impl BatchingXdotoolBackend {
+    pub fn new() -> BatchingXdotoolBackend {
+        BatchingXdotoolBackend::with_program("xdotool", DEFAULT_BATCH_SIZE)
+    }
+
+    /// `program` must understand xdotool's command chaining.
+    pub fn with_program(program: &str, batch_size: usize) -> BatchingXdotoolBackend {
+        BatchingXdotoolBackend {
+            program: program.to_string(),
+            batch_size: batch_size.max(1),
+            args: Vec::new(),
+            commands: 0,
+        }
+    }
+
+    /// Arguments of the commands which haven't been sent yet.
+    pub fn pending_args(&self) -> &[String] {
+        &self.args
+    }
+
+    fn push(&mut self, args: &[String]) -> Result<(), Error> {
+        self.args.extend_from_slice(args);
+        self.commands += 1;
+        if self.commands >= self.batch_size {
+            self.send()?;
+        }
+        Ok(())
+    }
+
+    fn send(&mut self) -> Result<(), Error> {
+        if self.args.is_empty() {
+            return Ok(());
+        }
+        let args = std::mem::take(&mut self.args);
+        self.commands = 0;
+        let status = Command::new(&self.program).args(&args).status()
+            .map_err(|error| Error::spawn(&self.program, error))?;
+        if !status.success() {
+            return Err(Error::backend(format!("{} finished with failure running {} commands",
+                                            self.program, args.join(" "))));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BatchingXdotoolBackend {
+    fn default() -> Self {
+        BatchingXdotoolBackend::new()
+    }
+}
+
+impl Drop for BatchingXdotoolBackend {
+    // A buffered mouseup mustn't get lost, otherwise the button stays pressed.
+    fn drop(&mut self) {
+        let _ = self.send();
+    }
+}
+
+impl PointerBackend for BatchingXdotoolBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.push(&["mousedown".to_string(), "1".to_string()])
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.push(&["mouseup".to_string(), "1".to_string()])
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.push(&["mousemove".to_string(), x.to_string(), y.to_string()])
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        self.send()?;
+        mouse::coords()
+    }
+
+    fn pause(&mut self, duration: Duration) -> Result<(), Error> {
+        self.push(&["sleep".to_string(), duration.as_secs_f32().to_string()])
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.send()
+    }
+}
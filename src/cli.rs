@@ -0,0 +1,433 @@
+use std::time::Duration;
+
+use error::Error;
+use rect::Rect;
+use fit::ScaleMode;
+use flatten;
+use merge;
+use simplify;
+use executor;
+use pacing::Pacing;
+use profiles;
+
+pub const USAGE: &str = "\
+Draws SVG images with the mouse.
+
+Usage: svg-bot <command> [options]
+
+Commands:
+  draw <svg>          Draw the SVG with the mouse
+  calibrate <name>    Save the paint area as a named profile
+  preview <svg>       Render the strokes the bot would draw as an SVG
+  info <svg>          Print size and stroke statistics of the SVG
+  record <svg>        Print pointer events of a simulated run with their timing
+  export <svg>        Print the stroke plan as JSON
+  help [command]      Print help of a command
+
+Run `svg-bot help <command>` for options of a command.";
+
+const AREA_OPTIONS: &str = "
+
+Paint area:
+  --area <x,y,w,h>          Paint area in screen pixels
+  --profile <name>          Paint area saved by `svg-bot calibrate`
+  --profiles <path>         Profiles file, ~/.config/svg-bot/profiles by default
+  --countdown               Capture the area corners after a countdown instead of on Enter";
+
+const CALIBRATE_OPTIONS: &str = "
+
+Options:
+  --countdown               Capture the corners after a countdown instead of on Enter
+  --profiles <path>         Profiles file, ~/.config/svg-bot/profiles by default";
+
+const OUTPUT_OPTIONS: &str = "
+
+Output:
+  -o, --output <path>       Write to a file instead of stdout";
+
+const PLAN_OPTIONS: &str = "
+
+Geometry:
+  --scale <mode>            auto, fit, fill, stretch or pixels per SVG unit [default: auto]
+  --margin <px>             Space left free on every side of the paint area [default: 0]
+  --tolerance <px>          Max deviation of flattened curves [default: 0.5]
+  --simplify <px>           Max deviation of simplified strokes, 0 keeps every point [default: 0.5]
+  --merge-tolerance <px>    Max gap between strokes joined into one [default: 1]
+  --keep-order              Draw strokes in document order without merging or reordering";
+
+const PACING_OPTIONS: &str = "
+
+Speed:
+  --speed <preset>          instant, fast, paint, browser or careful [default: instant]
+  --move-delay <ms>         Pause after every move
+  --press-delay <ms>        Pause before and after pressing the button
+  --release-delay <ms>      Pause before and after releasing the button
+  --max-step <px>           Split longer moves while drawing, 0 never splits";
+
+const RUN_OPTIONS: &str = "
+
+Drawing:
+  --backend <name>          xdotool, xdotool-batch or xtest [default: xdotool-batch]
+  --stop-threshold <px>     Stop when the mouse is moved this far away, 0 never stops [default: 20]
+  --journal <path>          Progress file [default: <svg>.journal]
+  --resume                  Continue an interrupted run in its paint area
+  --dry-run                 Don't touch the mouse, print the stroke plan as JSON";
+
+/// Which backend drives the pointer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BackendKind {
+    Xdotool,
+    XdotoolBatch,
+    XTest,
+}
+
+impl BackendKind {
+    pub fn parse(value: &str) -> Result<BackendKind, Error> {
+        match value {
+            "xdotool" => Ok(BackendKind::Xdotool),
+            "xdotool-batch" => Ok(BackendKind::XdotoolBatch),
+            "xtest" => Ok(BackendKind::XTest),
+            _ => Err(Error::Usage(format!("Backend must be xdotool, xdotool-batch or xtest, got '{}'", value))),
+        }
+    }
+}
+
+/// Where the paint area comes from. Without an area or a profile the corners are captured interactively.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AreaOptions {
+    pub area: Option<Rect>,
+    pub profile: Option<String>,
+    pub profiles_path: Option<String>,
+    pub countdown: bool,
+}
+
+/// How an SVG is turned into a stroke plan.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanOptions {
+    pub svg: String,
+    pub area: AreaOptions,
+    pub scale_mode: ScaleMode,
+    pub margin: f32,
+    pub tolerance: f32,
+    pub simplify: f32,
+    pub merge_tolerance: f32,
+    pub keep_order: bool,
+}
+
+/// How a stroke plan is drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunOptions {
+    pub pacing: Pacing,
+    pub backend: BackendKind,
+    pub stop_threshold: Option<f32>,
+    pub journal: Option<String>,
+    pub resume: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Draw(PlanOptions, RunOptions),
+    Calibrate { name: String, countdown: bool, profiles_path: Option<String> },
+    Preview(PlanOptions, Option<String>),
+    Info(PlanOptions),
+    Record(PlanOptions, Pacing),
+    Export(PlanOptions, Option<String>),
+    /// Help of the given command or the general one.
+    Help(Option<String>),
+}
+
+/// Parses command line arguments, without the program name.
+pub fn parse<I>(args: I) -> Result<Command, Error> where I: IntoIterator<Item = String> {
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Err(Error::Usage("Command is required, run `svg-bot help` for the list".to_string())),
+    };
+    let args: Vec<String> = args.collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        return Ok(Command::Help(Some(command)));
+    }
+    match command.as_str() {
+        "help" | "--help" | "-h" => Ok(Command::Help(args.into_iter().next())),
+        "draw" => parse_draw(args),
+        "calibrate" => parse_calibrate(args),
+        "preview" => {
+            let mut parser = Parser::new("preview", args);
+            let mut output = None;
+            let plan = parse_plan(&mut parser, |parser, option| match option {
+                "--output" | "-o" => {
+                    output = Some(parser.value(option)?);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })?;
+            Ok(Command::Preview(plan, output))
+        }
+        "info" => {
+            let mut parser = Parser::new("info", args);
+            Ok(Command::Info(parse_plan(&mut parser, |_, _| Ok(false))?))
+        }
+        "record" => {
+            let mut parser = Parser::new("record", args);
+            let mut pacing = PacingOptions::default();
+            let plan = parse_plan(&mut parser, |parser, option| pacing.parse(parser, option))?;
+            Ok(Command::Record(plan, pacing.pacing()))
+        }
+        "export" => {
+            let mut parser = Parser::new("export", args);
+            let mut output = None;
+            let plan = parse_plan(&mut parser, |parser, option| match option {
+                "--output" | "-o" => {
+                    output = Some(parser.value(option)?);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            })?;
+            Ok(Command::Export(plan, output))
+        }
+        _ => Err(Error::Usage(format!("Unknown command '{}', run `svg-bot help` for the list", command))),
+    }
+}
+
+/// Help text of `command`, or the general one for None.
+pub fn usage(command: Option<&str>) -> Result<String, Error> {
+    let text = match command {
+        None | Some("help") => USAGE.to_string(),
+        Some("draw") => command_usage("Draws the SVG with the mouse.", "draw <svg>",
+                                      &[AREA_OPTIONS, PLAN_OPTIONS, PACING_OPTIONS, RUN_OPTIONS]),
+        Some("calibrate") => command_usage(
+            "Captures the paint area from the mouse put on its corners and saves it as a profile.",
+            "calibrate <name>", &[CALIBRATE_OPTIONS]),
+        Some("preview") => command_usage(
+            "Renders the strokes as an SVG: pen-down strokes in black, pen-up travels dashed red.\n\
+             Without an area the SVG keeps its own size.",
+            "preview <svg>", &[OUTPUT_OPTIONS, AREA_OPTIONS, PLAN_OPTIONS]),
+        Some("info") => command_usage("Prints size of the SVG and statistics of its strokes.",
+                                      "info <svg>", &[AREA_OPTIONS, PLAN_OPTIONS]),
+        Some("record") => command_usage(
+            "Prints pointer events of a simulated run, one per line, with milliseconds since the start.\n\
+             Nothing touches the mouse.",
+            "record <svg>", &[AREA_OPTIONS, PLAN_OPTIONS, PACING_OPTIONS]),
+        Some("export") => command_usage("Prints the stroke plan as JSON.",
+                                        "export <svg>", &[OUTPUT_OPTIONS, AREA_OPTIONS, PLAN_OPTIONS]),
+        Some(command) => return Err(Error::Usage(format!("Unknown command '{}', run `svg-bot help` for the list", command))),
+    };
+    Ok(text)
+}
+
+fn command_usage(summary: &str, usage: &str, sections: &[&str]) -> String {
+    format!("{}\n\nUsage: svg-bot {} [options]{}", summary, usage, sections.concat())
+}
+
+fn parse_draw(args: Vec<String>) -> Result<Command, Error> {
+    let mut parser = Parser::new("draw", args);
+    let mut run = RunOptions {
+        pacing: Pacing::default(),
+        backend: BackendKind::XdotoolBatch,
+        stop_threshold: Some(executor::DEFAULT_STOP_THRESHOLD),
+        journal: None,
+        resume: false,
+        dry_run: false,
+    };
+    let mut pacing = PacingOptions::default();
+    let plan = parse_plan(&mut parser, |parser, option| {
+        match option {
+            "--backend" => run.backend = BackendKind::parse(&parser.value(option)?)?,
+            "--stop-threshold" => {
+                let threshold = parser.number(option)?;
+                run.stop_threshold = if threshold > 0f32 { Some(threshold) } else { None };
+            }
+            "--journal" => run.journal = Some(parser.value(option)?),
+            "--resume" => run.resume = true,
+            "--dry-run" => run.dry_run = true,
+            _ => return pacing.parse(parser, option),
+        }
+        Ok(true)
+    })?;
+    run.pacing = pacing.pacing();
+    if run.resume && (plan.area.area.is_some() || plan.area.profile.is_some()) {
+        return Err(Error::Usage(
+            "--resume draws into the area of the interrupted run, it can't be combined with --area or --profile".to_string()));
+    }
+    if run.dry_run && plan.area.countdown {
+        return Err(Error::Usage("--dry-run doesn't touch the mouse, so it can't capture the area after --countdown".to_string()));
+    }
+    Ok(Command::Draw(plan, run))
+}
+
+fn parse_calibrate(args: Vec<String>) -> Result<Command, Error> {
+    let mut parser = Parser::new("calibrate", args);
+    let mut name = None;
+    let mut countdown = false;
+    let mut profiles_path = None;
+    while let Some(arg) = parser.next() {
+        match arg.as_str() {
+            "--countdown" => countdown = true,
+            "--profiles" => profiles_path = Some(parser.value(&arg)?),
+            _ if arg.starts_with('-') => return Err(unknown_option(&arg, "calibrate")),
+            _ if name.is_none() => name = Some(arg),
+            _ => return Err(Error::Usage(format!("Unexpected argument '{}'", arg))),
+        }
+    }
+    match name {
+        Some(name) => {
+            // Checked before the user is asked to capture the corners.
+            profiles::validate_name(&name)?;
+            Ok(Command::Calibrate{ name, countdown, profiles_path })
+        }
+        None => Err(Error::Usage("Profile name is required, e.g. `svg-bot calibrate paint`".to_string())),
+    }
+}
+
+/// Parses options shared by every command working on a stroke plan,
+/// `extra` gets the rest and returns whether it knew the option.
+fn parse_plan<F>(parser: &mut Parser, mut extra: F) -> Result<PlanOptions, Error>
+        where F: FnMut(&mut Parser, &str) -> Result<bool, Error> {
+    let mut svg = None;
+    let mut options = PlanOptions {
+        svg: String::new(),
+        area: AreaOptions::default(),
+        scale_mode: ScaleMode::Auto,
+        margin: 0f32,
+        tolerance: flatten::DEFAULT_TOLERANCE,
+        simplify: simplify::DEFAULT_EPSILON,
+        merge_tolerance: merge::DEFAULT_MERGE_TOLERANCE,
+        keep_order: false,
+    };
+    while let Some(arg) = parser.next() {
+        match arg.as_str() {
+            "--area" => options.area.area = Some(profiles::parse_area(&parser.value(&arg)?)?),
+            "--profile" => options.area.profile = Some(parser.value(&arg)?),
+            "--profiles" => options.area.profiles_path = Some(parser.value(&arg)?),
+            "--countdown" => options.area.countdown = true,
+            "--scale" => options.scale_mode = ScaleMode::parse(&parser.value(&arg)?)?,
+            "--margin" => options.margin = parser.non_negative(&arg)?,
+            "--tolerance" => {
+                options.tolerance = parser.number(&arg)?;
+                if options.tolerance <= 0f32 {
+                    return Err(Error::Usage("--tolerance must be positive".to_string()));
+                }
+            }
+            "--simplify" => options.simplify = parser.non_negative(&arg)?,
+            "--merge-tolerance" => options.merge_tolerance = parser.non_negative(&arg)?,
+            "--keep-order" => options.keep_order = true,
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                if !extra(parser, &arg)? {
+                    return Err(unknown_option(&arg, parser.command()));
+                }
+            }
+            _ if svg.is_none() => svg = Some(arg),
+            _ => return Err(Error::Usage(format!("Unexpected argument '{}'", arg))),
+        }
+    }
+    options.svg = svg.ok_or_else(|| Error::Usage("Path to SVG is required".to_string()))?;
+    Ok(options)
+}
+
+/// Pacing options as given. The preset is the base the individual delays are applied to,
+/// whatever their order on the command line.
+#[derive(Default)]
+struct PacingOptions {
+    preset: Option<Pacing>,
+    move_delay: Option<Duration>,
+    press_delay: Option<Duration>,
+    release_delay: Option<Duration>,
+    max_step: Option<Option<f32>>,
+}
+
+impl PacingOptions {
+    fn parse(&mut self, parser: &mut Parser, option: &str) -> Result<bool, Error> {
+        match option {
+            "--speed" => self.preset = Some(Pacing::preset(&parser.value(option)?)?),
+            "--move-delay" => self.move_delay = Some(parser.millis(option)?),
+            "--press-delay" => self.press_delay = Some(parser.millis(option)?),
+            "--release-delay" => self.release_delay = Some(parser.millis(option)?),
+            "--max-step" => {
+                let max_step = parser.non_negative(option)?;
+                self.max_step = Some(if max_step > 0f32 { Some(max_step) } else { None });
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn pacing(&self) -> Pacing {
+        let mut pacing = self.preset.unwrap_or_default();
+        if let Some(move_delay) = self.move_delay {
+            pacing = pacing.with_move_delay(move_delay);
+        }
+        if let Some(press_delay) = self.press_delay {
+            pacing = pacing.with_press_delay(press_delay);
+        }
+        if let Some(release_delay) = self.release_delay {
+            pacing = pacing.with_release_delay(release_delay);
+        }
+        if let Some(max_step) = self.max_step {
+            pacing = pacing.with_max_step(max_step);
+        }
+        pacing
+    }
+}
+
+fn unknown_option(option: &str, command: &str) -> Error {
+    Error::Usage(format!("Unknown option '{}', run `svg-bot help {}` for the list", option, command))
+}
+
+/// Arguments of a command, with `--option=value` split into two.
+struct Parser {
+    args: Vec<String>,
+    position: usize,
+    command: &'static str,
+}
+
+impl Parser {
+    fn new(command: &'static str, args: Vec<String>) -> Parser {
+        let args = args.into_iter().flat_map(|arg| {
+            if arg.starts_with("--") {
+                if let Some(equals) = arg.find('=') {
+                    return vec![arg[..equals].to_string(), arg[equals + 1..].to_string()];
+                }
+            }
+            vec![arg]
+        }).collect();
+        Parser{ args, position: 0, command }
+    }
+
+    fn command(&self) -> &str {
+        self.command
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let arg = self.args.get(self.position).cloned();
+        self.position += 1;
+        arg
+    }
+
+    fn value(&mut self, option: &str) -> Result<String, Error> {
+        self.next().ok_or_else(|| Error::Usage(format!("{} needs a value", option)))
+    }
+
+    fn number(&mut self, option: &str) -> Result<f32, Error> {
+        let value = self.value(option)?;
+        match value.parse::<f32>() {
+            Ok(number) if number.is_finite() => Ok(number),
+            _ => Err(Error::Usage(format!("{} needs a number, got '{}'", option, value))),
+        }
+    }
+
+    fn non_negative(&mut self, option: &str) -> Result<f32, Error> {
+        let number = self.number(option)?;
+        if number < 0f32 {
+            return Err(Error::Usage(format!("{} can't be negative", option)));
+        }
+        Ok(number)
+    }
+
+    fn millis(&mut self, option: &str) -> Result<Duration, Error> {
+        let value = self.value(option)?;
+        value.parse::<u64>()
+            .map(Duration::from_millis)
+            .map_err(|_| Error::Usage(format!("{} needs whole milliseconds, got '{}'", option, value)))
+    }
+}
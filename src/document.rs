@@ -0,0 +1,129 @@
+use svg::node::Attributes;
+use svg::parser::Event;
+use svg::node::element::tag::{self, Type};
+
+use rect::Rect;
+use error::Error;
+use painter::Painter;
+use transform::Transform;
+use stroke_plan::{Stroke, StrokePlan};
+use shapes;
+use size;
+use flatten;
+
+const MIN_BOUNDS_SIZE: f32 = 1e-3f32;
+// Elements whose content is only drawn when referenced, e.g. by `use` or `clip-path`, which isn't supported.
+const NOT_RENDERED_CONTAINERS: [&str; 6] = [tag::Definitions, tag::ClipPath, tag::Mask, tag::Symbol, tag::Marker, "pattern"];
+
+// Inherited state of an open element.
+#[derive(Clone)]
+struct ElementState {
+    transform: Transform,
+    color: Option<String>,
+    // Area percentages are relative to, declared by the closest `svg` element.
+    viewport: Option<Rect>,
+    // Inside a container which isn't rendered.
+    not_rendered: bool,
+}
+
+/// Turns every drawable element of a parsed SVG document into strokes.
+/// `svg_to_screen` maps the SVG's viewport onto the screen.
+/// Transforms of the elements and of the groups they are nested in are applied to their geometry before it.
+pub fn compile<'l, I>(events: I, svg_to_screen: &Transform) -> Result<StrokePlan, Error>
+        where I: IntoIterator<Item = Event<'l>> {
+    compile_with_tolerance(events, svg_to_screen, flatten::DEFAULT_TOLERANCE)
+}
+
+/// Same as `compile`, curves are flattened with `tolerance` screen pixels.
+pub fn compile_with_tolerance<'l, I>(events: I, svg_to_screen: &Transform, tolerance: f32) -> Result<StrokePlan, Error>
+        where I: IntoIterator<Item = Event<'l>> {
+    let mut plan = StrokePlan::default();
+    // State of every currently open element.
+    let mut states = vec![ElementState{ transform: *svg_to_screen, color: None, viewport: None, not_rendered: false }];
+    for event in events {
+        match event {
+            Event::Tag(_, Type::End, _) if states.len() > 1 => {
+                states.pop();
+            }
+            Event::Tag(_, Type::End, _) => {}
+            Event::Tag(name, tag_type, attributes) => {
+                let mut state = states.last().unwrap().clone();
+                if let Some(element_transform) = attributes.get("transform") {
+                    state.transform = state.transform.multiply(&Transform::parse(element_transform)?);
+                }
+                if let Some(color) = stroke_color(&attributes) {
+                    state.color = Some(color);
+                }
+                if name == tag::SVG {
+                    state.viewport = size::root_area(&attributes)?;
+                }
+                if NOT_RENDERED_CONTAINERS.contains(&name) {
+                    state.not_rendered = true;
+                }
+                if tag_type == Type::Start {
+                    states.push(state.clone());
+                }
+
+                if state.not_rendered {
+                    continue;
+                }
+                if let Some(data) = shapes::element_data(name, &attributes, state.viewport.as_ref())? {
+                    let mut painter = Painter::new(state.transform);
+                    painter.set_tolerance(tolerance);
+                    let element_id = attributes.get("id").map(|id| id.to_string());
+                    for (index, command) in data.iter().enumerate() {
+                        painter.perform_command(command).map_err(|error| {
+                            // Other shapes have no path data of their own to point into.
+                            let offset = attributes.get("d")
+                                .filter(|_| name == tag::Path)
+                                .and_then(|path_data| shapes::command_offset(path_data, index));
+                            error.at(element_id.as_deref(), offset)
+                        })?;
+                    }
+                    for polyline in painter.into_polylines() {
+                        plan.push(Stroke::new(polyline, element_id.clone(), state.color.clone()));
+                    }
+                }
+            }
+            Event::Error(error) => {
+                return Err(Error::from(error));
+            }
+            _ => {}
+        }
+    }
+    Ok(plan)
+}
+
+/// Bounding box of everything the document draws, in the SVG's user units.
+/// Returns None if the document draws nothing.
+pub fn content_bounds<'l, I>(events: I) -> Result<Option<Rect>, Error>
+        where I: IntoIterator<Item = Event<'l>> {
+    let plan = compile(events, &Transform::identity())?;
+
+    let points = plan.strokes().iter().flat_map(|stroke| stroke.points().iter()).collect::<Vec<_>>();
+    if points.is_empty() {
+        return Ok(None);
+    }
+    let min_x = points.iter().map(|point| point.x()).fold(f32::MAX, f32::min);
+    let min_y = points.iter().map(|point| point.y()).fold(f32::MAX, f32::min);
+    let max_x = points.iter().map(|point| point.x()).fold(f32::MIN, f32::max);
+    let max_y = points.iter().map(|point| point.y()).fold(f32::MIN, f32::max);
+    // A straight line has no width or height, but the area must have some to be mapped onto the screen.
+    Ok(Some(Rect::new(min_x, min_y, (max_x - min_x).max(MIN_BOUNDS_SIZE), (max_y - min_y).max(MIN_BOUNDS_SIZE))))
+}
+
+// Color from the `stroke` attribute or the `stroke` property of the `style` attribute, the latter wins.
+fn stroke_color(attributes: &Attributes) -> Option<String> {
+    let from_style = attributes.get("style").and_then(|style| {
+        style.split(';')
+            .filter_map(|declaration| {
+                let mut parts = declaration.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) if name.trim() == "stroke" => Some(value.trim().to_string()),
+                    _ => None,
+                }
+            })
+            .next_back()
+    });
+    from_style.or_else(|| attributes.get("stroke").map(|stroke| stroke.trim().to_string()))
+}
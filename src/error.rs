@@ -0,0 +1,141 @@
+use std::error;
+use std::fmt;
+use std::io;
+use svg;
+use x11rb;
+
+/// Everything which can go wrong while turning an SVG into strokes and drawing them.
+#[derive(Debug)]
+pub enum Error {
+    /// A file or a stream couldn't be read or written.
+    Io { context: Option<String>, source: io::Error },
+    /// Driving the pointer failed: xdotool, the X server or signal handling.
+    Backend { message: String, source: Option<Box<dyn error::Error + Send + Sync>> },
+    /// The SVG document isn't well-formed.
+    Xml(svg::parser::Error),
+    /// An attribute, a file or an option value couldn't be parsed.
+    Parse(String),
+    /// Path data of an element is invalid.
+    /// `offset` is the byte offset into the `d` attribute of the command the error is in.
+    PathData { element_id: Option<String>, offset: Option<usize>, message: String, source: Option<svg::parser::Error> },
+    /// The SVG uses something which can't be drawn.
+    Unsupported(String),
+    /// The paint area couldn't be captured or found.
+    Calibration(String),
+    /// A journal is corrupted or belongs to another drawing.
+    Journal(String),
+    /// Command line arguments are invalid.
+    Usage(String),
+}
+
+impl Error {
+    pub fn io(context: String, source: io::Error) -> Error {
+        Error::Io{ context: Some(context), source }
+    }
+
+    pub fn backend(message: String) -> Error {
+        Error::Backend{ message, source: None }
+    }
+
+    /// `command` couldn't be started, e.g. because its program isn't installed.
+    pub fn spawn(command: &str, source: io::Error) -> Error {
+        Error::Backend{ message: format!("Couldn't run {}", command), source: Some(Box::new(source)) }
+    }
+
+    /// Path data error of a command, the caller knows which element and command it is in.
+    pub fn path_data(message: String) -> Error {
+        Error::PathData{ element_id: None, offset: None, message, source: None }
+    }
+
+    /// Fills in where a path data error happened, other errors are returned as they are.
+    pub fn at(self, element_id: Option<&str>, offset: Option<usize>) -> Error {
+        match self {
+            Error::PathData{ element_id: None, offset: None, message, source } => Error::PathData {
+                element_id: element_id.map(String::from),
+                offset,
+                message,
+                source,
+            },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    // Sources aren't included, they are reported by whoever prints the chain.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io{ context: Some(ref context), .. } => write!(f, "{}", context),
+            Error::Io{ context: None, .. } => write!(f, "I/O failed"),
+            Error::Backend{ ref message, .. } => write!(f, "{}", message),
+            Error::Xml(_) => write!(f, "SVG document is malformed"),
+            Error::PathData{ ref element_id, offset, ref message, .. } => {
+                write!(f, "Invalid path data")?;
+                if let Some(ref element_id) = *element_id {
+                    write!(f, " of element '{}'", element_id)?;
+                }
+                if let Some(offset) = offset {
+                    write!(f, " at offset {}", offset)?;
+                }
+                write!(f, ": {}", message)
+            }
+            Error::Parse(ref message) | Error::Unsupported(ref message) | Error::Calibration(ref message)
+                | Error::Journal(ref message) | Error::Usage(ref message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io{ ref source, .. } => Some(source),
+            Error::Backend{ source: Some(ref source), .. } => Some(source.as_ref()),
+            Error::Xml(ref source) => Some(source),
+            Error::PathData{ source: Some(ref source), .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io{ context: None, source: error }
+    }
+}
+
+impl From<svg::parser::Error> for Error {
+    fn from(error: svg::parser::Error) -> Self {
+        Error::Xml(error)
+    }
+}
+
+impl From<x11rb::errors::ConnectError> for Error {
+    fn from(error: x11rb::errors::ConnectError) -> Self {
+        Error::Backend{ message: "Couldn't connect to X server".to_string(), source: Some(Box::new(error)) }
+    }
+}
+
+impl From<x11rb::errors::ConnectionError> for Error {
+    fn from(error: x11rb::errors::ConnectionError) -> Self {
+        Error::Backend{ message: "X server connection failed".to_string(), source: Some(Box::new(error)) }
+    }
+}
+
+impl From<x11rb::errors::ReplyError> for Error {
+    fn from(error: x11rb::errors::ReplyError) -> Self {
+        Error::Backend{ message: "X server request failed".to_string(), source: Some(Box::new(error)) }
+    }
+}
+
+/// `error` followed by everything it was caused by, e.g. "Couldn't open a.svg: No such file or directory".
+pub fn chain(error: &dyn error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
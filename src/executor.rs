@@ -0,0 +1,274 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use error::Error;
+use backend::PointerBackend;
+use interrupt::InterruptFlag;
+use journal::Journal;
+use pacing::Pacing;
+use pause::PauseSwitch;
+use point::ScreenPoint;
+use stroke_plan::StrokePlan;
+
+/// Default distance in pixels the pointer may be away from where the executor put it
+/// before drawing is considered taken over by the user.
+pub const DEFAULT_STOP_THRESHOLD: f32 = 20f32;
+
+/// Where drawing was stopped because the pointer wasn't where it was expected to be.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StopPoint {
+    stroke: usize,
+    point: usize,
+    expected: ScreenPoint,
+    actual: ScreenPoint,
+}
+
+impl StopPoint {
+    /// Index of the stroke which was being drawn or travelled to.
+    pub fn stroke(&self) -> usize {
+        self.stroke
+    }
+
+    /// Index of the stroke's point which hadn't been reached yet.
+    pub fn point(&self) -> usize {
+        self.point
+    }
+
+    /// Where the executor had put the pointer.
+    pub fn expected(&self) -> &ScreenPoint {
+        &self.expected
+    }
+
+    /// Where the pointer was found.
+    pub fn actual(&self) -> &ScreenPoint {
+        &self.actual
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Finished,
+    Stopped(StopPoint),
+    /// The interrupt flag was set, the pen was lifted.
+    Interrupted,
+}
+
+/// Replays stroke plans on a pointer backend.
+pub struct Executor<'a, B: 'a + PointerBackend> {
+    backend: &'a mut B,
+    pacing: Pacing,
+    stop_threshold: Option<f32>,
+    stop_check_interval: Duration,
+    expected: Option<ScreenPoint>,
+    last_check: Option<Instant>,
+    first_stroke: usize,
+    journal: Option<Journal>,
+    journal_interval: Duration,
+    last_journal_save: Option<Instant>,
+    // Strokes completed but not saved in the journal yet.
+    unsaved_completed: Option<usize>,
+    pause_switch: Option<PauseSwitch>,
+    interrupt_flag: Option<InterruptFlag>,
+}
+
+impl<'a, B: PointerBackend> Executor<'a, B> {
+    pub fn new(backend: &'a mut B) -> Executor<'a, B> {
+        Executor {
+            backend,
+            pacing: Pacing::default(),
+            stop_threshold: None,
+            stop_check_interval: Duration::from_millis(0),
+            expected: None,
+            last_check: None,
+            first_stroke: 0,
+            journal: None,
+            journal_interval: Duration::from_millis(0),
+            last_journal_save: None,
+            unsaved_completed: None,
+            pause_switch: None,
+            interrupt_flag: None,
+        }
+    }
+
+    pub fn set_pacing(&mut self, pacing: Pacing) {
+        self.pacing = pacing;
+    }
+
+    /// Stops drawing when the pointer is found further than `threshold` pixels away from where it was put,
+    /// which means the user grabbed the mouse. None never stops.
+    pub fn set_stop_threshold(&mut self, threshold: Option<f32>) {
+        self.stop_threshold = threshold;
+    }
+
+    /// Minimal time between pointer position checks.
+    pub fn set_stop_check_interval(&mut self, interval: Duration) {
+        self.stop_check_interval = interval;
+    }
+
+    /// Skips strokes before `first_stroke`, e.g. the ones an interrupted run has already drawn.
+    pub fn set_first_stroke(&mut self, first_stroke: usize) {
+        self.first_stroke = first_stroke;
+    }
+
+    /// Journal to record every completed stroke in.
+    pub fn set_journal(&mut self, journal: Journal) {
+        self.journal = Some(journal);
+    }
+
+    /// Minimal time between journal saves, strokes completed in between are saved together.
+    /// The journal is saved in any case when drawing pauses or ends.
+    pub fn set_journal_interval(&mut self, interval: Duration) {
+        self.journal_interval = interval;
+    }
+
+    pub fn take_journal(&mut self) -> Option<Journal> {
+        self.journal.take()
+    }
+
+    /// Switch checked before every stroke, drawing waits with the pen lifted while it's on.
+    pub fn set_pause_switch(&mut self, pause_switch: PauseSwitch) {
+        self.pause_switch = Some(pause_switch);
+    }
+
+    /// Flag checked as often as the pointer position, drawing stops with the pen lifted once it's set.
+    pub fn set_interrupt_flag(&mut self, interrupt_flag: InterruptFlag) {
+        self.interrupt_flag = Some(interrupt_flag);
+    }
+
+    /// Draws every stroke of the plan, lifting the pen between them.
+    /// When the user takes the mouse over the pen is lifted and the place where drawing stopped is returned.
+    pub fn execute(&mut self, plan: &StrokePlan) -> Result<Outcome, Error> {
+        let outcome = self.draw(plan)?;
+        self.save_journal(true)?;
+        Ok(outcome)
+    }
+
+    fn draw(&mut self, plan: &StrokePlan) -> Result<Outcome, Error> {
+        for (stroke_index, stroke) in plan.strokes().iter().enumerate().skip(self.first_stroke) {
+            let points = stroke.points();
+            if points.is_empty() {
+                continue;
+            }
+            self.wait_while_paused()?;
+            if self.is_interrupted() {
+                self.backend.flush()?;
+                return Ok(Outcome::Interrupted);
+            }
+            if let Some(stop) = self.check_position(stroke_index, 0)? {
+                return Ok(Outcome::Stopped(stop));
+            }
+            self.move_to(&points[0])?;
+            self.pause(self.pacing.press_delay())?;
+            self.backend.press()?;
+            self.pause(self.pacing.press_delay())?;
+            for (point_index, segment) in points.windows(2).enumerate() {
+                for step in steps(&segment[0], &segment[1], self.pacing.max_step()) {
+                    if self.is_interrupted() {
+                        self.backend.release()?;
+                        self.backend.flush()?;
+                        return Ok(Outcome::Interrupted);
+                    }
+                    if let Some(stop) = self.check_position(stroke_index, point_index + 1)? {
+                        self.backend.release()?;
+                        self.backend.flush()?;
+                        return Ok(Outcome::Stopped(stop));
+                    }
+                    self.move_to(&step)?;
+                }
+            }
+            self.pause(self.pacing.release_delay())?;
+            self.backend.release()?;
+            self.pause(self.pacing.release_delay())?;
+            self.unsaved_completed = Some(stroke_index + 1);
+            self.save_journal(false)?;
+        }
+        self.backend.flush()?;
+        Ok(Outcome::Finished)
+    }
+
+    fn wait_while_paused(&mut self) -> Result<(), Error> {
+        let paused = match self.pause_switch {
+            Some(ref switch) => switch.is_paused(),
+            None => false,
+        };
+        if !paused {
+            return Ok(());
+        }
+        self.backend.flush()?;
+        self.save_journal(true)?;
+        while self.pause_switch.as_ref().is_some_and(|switch| switch.is_paused()) && !self.is_interrupted() {
+            thread::sleep(Duration::from_millis(50));
+        }
+        // The user may well have used the mouse during the pause.
+        self.expected = None;
+        self.last_check = None;
+        Ok(())
+    }
+
+    fn save_journal(&mut self, force: bool) -> Result<(), Error> {
+        let completed = match self.unsaved_completed {
+            Some(completed) => completed,
+            None => return Ok(()),
+        };
+        if !force && self.last_journal_save.is_some_and(|last_save| last_save.elapsed() < self.journal_interval) {
+            return Ok(());
+        }
+        if let Some(ref mut journal) = self.journal {
+            // Strokes only count as completed once they have really been drawn.
+            self.backend.flush()?;
+            journal.set_completed(completed)?;
+        }
+        self.last_journal_save = Some(Instant::now());
+        self.unsaved_completed = None;
+        Ok(())
+    }
+
+    fn is_interrupted(&self) -> bool {
+        self.interrupt_flag.as_ref().is_some_and(|flag| flag.is_interrupted())
+    }
+
+    fn check_position(&mut self, stroke: usize, point: usize) -> Result<Option<StopPoint>, Error> {
+        let (threshold, expected) = match (self.stop_threshold, self.expected.clone()) {
+            (Some(threshold), Some(expected)) => (threshold, expected),
+            _ => return Ok(None),
+        };
+        if let Some(last_check) = self.last_check {
+            if last_check.elapsed() < self.stop_check_interval {
+                return Ok(None);
+            }
+        }
+        self.last_check = Some(Instant::now());
+        let (x, y) = self.backend.position()?;
+        let actual = ScreenPoint::new(x, y);
+        // Backends report whole pixels, so rounding alone mustn't stop drawing.
+        if actual.distance_to(&expected) > threshold.max(1f32) {
+            return Ok(Some(StopPoint{ stroke, point, expected, actual }));
+        }
+        Ok(None)
+    }
+
+    fn move_to(&mut self, point: &ScreenPoint) -> Result<(), Error> {
+        self.backend.move_to(point.x(), point.y())?;
+        self.expected = Some(point.clone());
+        self.pause(self.pacing.move_delay())
+    }
+
+    fn pause(&mut self, duration: Duration) -> Result<(), Error> {
+        if duration > Duration::from_millis(0) {
+            self.backend.pause(duration)?;
+        }
+        Ok(())
+    }
+}
+
+/// Points to move through from `start` to `end` with no step longer than `max_step`, `start` excluded.
+fn steps(start: &ScreenPoint, end: &ScreenPoint, max_step: Option<f32>) -> Vec<ScreenPoint> {
+    let count = match max_step {
+        Some(max_step) if max_step > 0f32 => (start.distance_to(end) / max_step).ceil().max(1f32) as usize,
+        _ => 1,
+    };
+    (1..=count).map(|i| {
+        let t = i as f32 / count as f32;
+        ScreenPoint::new(start.x() + (end.x() - start.x()) * t, start.y() + (end.y() - start.y()) * t)
+    }).collect()
+}
@@ -0,0 +1,162 @@
+use error::Error;
+use rect::Rect;
+use transform::Transform;
+
+/// Alignment of the viewBox along one axis of the viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Min,
+    Mid,
+    Max,
+}
+
+/// Value of the `preserveAspectRatio` attribute.
+/// See https://www.w3.org/TR/SVG/coords.html#PreserveAspectRatioAttribute
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PreserveAspectRatio {
+    // None stands for "none", i.e. non-uniform scaling.
+    align: Option<(Align, Align)>,
+    slice: bool,
+}
+
+impl PreserveAspectRatio {
+    pub fn new(align: Option<(Align, Align)>, slice: bool) -> PreserveAspectRatio {
+        PreserveAspectRatio{ align, slice }
+    }
+
+    /// Alignments along x and y, None if the aspect ratio isn't preserved.
+    pub fn align(&self) -> Option<(Align, Align)> {
+        self.align
+    }
+
+    /// Whether the viewBox covers the whole viewport (slice) instead of fitting into it (meet).
+    pub fn slice(&self) -> bool {
+        self.slice
+    }
+
+    /// Parses e.g. "xMidYMid meet", "xMinYMax slice" or "none".
+    pub fn parse(value: &str) -> Result<PreserveAspectRatio, Error> {
+        let error = || Error::Parse(format!("Couldn't parse preserveAspectRatio '{}'", value));
+        let mut words = value.split_whitespace().peekable();
+        if words.peek() == Some(&"defer") {
+            words.next();
+        }
+        let align = match words.next() {
+            Some("none") => None,
+            Some(align) if align.len() == 8 && align.starts_with('x') && &align[4..5] == "Y" => {
+                let x = parse_align(&align[1..4]).ok_or_else(error)?;
+                let y = parse_align(&align[5..8]).ok_or_else(error)?;
+                Some((x, y))
+            }
+            _ => return Err(error()),
+        };
+        let slice = match words.next() {
+            None | Some("meet") => false,
+            Some("slice") => true,
+            Some(_) => return Err(error()),
+        };
+        if words.next().is_some() {
+            return Err(error());
+        }
+        Ok(PreserveAspectRatio::new(align, slice))
+    }
+}
+
+impl Default for PreserveAspectRatio {
+    /// "xMidYMid meet", as the spec says.
+    fn default() -> Self {
+        PreserveAspectRatio::new(Some((Align::Mid, Align::Mid)), false)
+    }
+}
+
+fn parse_align(align: &str) -> Option<Align> {
+    match align {
+        "Min" => Some(Align::Min),
+        "Mid" => Some(Align::Mid),
+        "Max" => Some(Align::Max),
+        _ => None,
+    }
+}
+
+/// How the SVG is scaled into the paint area.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScaleMode {
+    /// Follow the SVG's `preserveAspectRatio`.
+    Auto,
+    /// Keep aspect ratio and fit the whole SVG into the area.
+    Fit,
+    /// Keep aspect ratio and cover the whole area, parts of the SVG may end up outside of it.
+    Fill,
+    /// Stretch each axis independently to the area.
+    Stretch,
+    /// Keep aspect ratio and use the given number of screen pixels per SVG unit.
+    Fixed(f32),
+}
+
+impl ScaleMode {
+    /// Parses "auto", "fit", "fill", "stretch" or a fixed positive scale like "1.5".
+    pub fn parse(value: &str) -> Result<ScaleMode, Error> {
+        match value {
+            "auto" => Ok(ScaleMode::Auto),
+            "fit" => Ok(ScaleMode::Fit),
+            "fill" => Ok(ScaleMode::Fill),
+            "stretch" => Ok(ScaleMode::Stretch),
+            _ => match value.parse::<f32>() {
+                Ok(scale) if scale > 0f32 && scale.is_finite() => Ok(ScaleMode::Fixed(scale)),
+                _ => Err(Error::Parse(format!(
+                    "Scale mode must be auto, fit, fill, stretch or a positive number, got '{}'", value))),
+            },
+        }
+    }
+}
+
+/// Transform which places `view_box` into `paint_area` shrunk by `margin` pixels on every side.
+/// Alignment always comes from `aspect_ratio`, `scale_mode` can override how the scale is chosen.
+/// Nothing is clipped: with slice or a big fixed scale strokes may go beyond the paint area.
+/// Fails if the margin leaves no room to draw in, everything would be squashed into a single point.
+pub fn svg_to_screen(view_box: &Rect, paint_area: &Rect,
+                     aspect_ratio: &PreserveAspectRatio, scale_mode: &ScaleMode,
+                     margin: f32) -> Result<Transform, Error> {
+    let viewport = Rect::new(paint_area.x() + margin,
+                             paint_area.y() + margin,
+                             paint_area.width() - 2f32 * margin,
+                             paint_area.height() - 2f32 * margin);
+    if viewport.width() <= 0f32 || viewport.height() <= 0f32 {
+        return Err(Error::Usage(format!("Margin of {}px leaves no room in the {}x{} paint area",
+                                        margin, paint_area.width(), paint_area.height())));
+    }
+    let scale_x = viewport.width() / view_box.width();
+    let scale_y = viewport.height() / view_box.height();
+
+    let (scale_x, scale_y) = match *scale_mode {
+        ScaleMode::Auto => match aspect_ratio.align() {
+            None => (scale_x, scale_y),
+            Some(_) if aspect_ratio.slice() => uniform(scale_x.max(scale_y)),
+            Some(_) => uniform(scale_x.min(scale_y)),
+        },
+        ScaleMode::Fit => uniform(scale_x.min(scale_y)),
+        ScaleMode::Fill => uniform(scale_x.max(scale_y)),
+        ScaleMode::Stretch => (scale_x, scale_y),
+        ScaleMode::Fixed(scale) => uniform(scale),
+    };
+
+    let (align_x, align_y) = aspect_ratio.align().unwrap_or((Align::Min, Align::Min));
+    let translate_x = viewport.x() - view_box.x() * scale_x
+        + aligned_offset(align_x, viewport.width() - view_box.width() * scale_x);
+    let translate_y = viewport.y() - view_box.y() * scale_y
+        + aligned_offset(align_y, viewport.height() - view_box.height() * scale_y);
+
+    Ok(Transform::translate(translate_x, translate_y).multiply(&Transform::scale(scale_x, scale_y)))
+}
+
+fn uniform(scale: f32) -> (f32, f32) {
+    (scale, scale)
+}
+
+fn aligned_offset(align: Align, free_space: f32) -> f32 {
+    match align {
+        Align::Min => 0f32,
+        Align::Mid => free_space / 2f32,
+        Align::Max => free_space,
+    }
+}
@@ -0,0 +1,47 @@
+use point::{SvgPoint, ScreenPoint};
+
+/// Default maximal distance in screen pixels between a curve and its flattened polyline.
+pub const DEFAULT_TOLERANCE: f32 = 0.5f32;
+
+// Limits subdivision to 2^16 segments per curve.
+const MAX_DEPTH: u32 = 16;
+
+/// Turns a parametric curve into points of a polyline which deviates from the curve
+/// by no more than `tolerance` screen pixels.
+/// `curve` must return the curve's point for `t` from 0 to 1,
+/// `to_screen` must map a point the same way it will be mapped when drawn.
+/// The start point (t = 0) isn't included into the result, the end point (t = 1) always is.
+pub fn flatten<F, M>(curve: F, to_screen: M, tolerance: f32) -> Vec<SvgPoint>
+        where F: Fn(f32) -> SvgPoint, M: Fn(&SvgPoint) -> ScreenPoint {
+    let start = to_screen(&curve(0f32));
+    let end = curve(1f32);
+    let mut points = Vec::new();
+    subdivide(&curve, &to_screen, tolerance, (0f32, 1f32), &start, end, 0, &mut points);
+    points
+}
+
+#[allow(clippy::too_many_arguments)]
+fn subdivide<F, M>(curve: &F, to_screen: &M, tolerance: f32, range: (f32, f32),
+                   start: &ScreenPoint, end: SvgPoint, depth: u32,
+                   points: &mut Vec<SvgPoint>)
+        where F: Fn(f32) -> SvgPoint, M: Fn(&SvgPoint) -> ScreenPoint {
+    let (t0, t1) = range;
+    let end_screen = to_screen(&end);
+    if depth < MAX_DEPTH {
+        // Checking quarters as well as the middle catches S-shaped pieces
+        // whose middle point happens to lie on the chord.
+        let exceeds_tolerance = [0.25f32, 0.5f32, 0.75f32].iter().any(|fraction| {
+            let sample = to_screen(&curve(t0 + (t1 - t0) * fraction));
+            sample.distance_to_segment(start, &end_screen) > tolerance
+        });
+        if exceeds_tolerance {
+            let middle_t = (t0 + t1) / 2f32;
+            let middle = curve(middle_t);
+            let middle_screen = to_screen(&middle);
+            subdivide(curve, to_screen, tolerance, (t0, middle_t), start, middle, depth + 1, points);
+            subdivide(curve, to_screen, tolerance, (middle_t, t1), &middle_screen, end, depth + 1, points);
+            return;
+        }
+    }
+    points.push(end);
+}
@@ -0,0 +1,52 @@
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use ctrlc;
+
+use error::Error;
+
+/// Exit code of a process ended by SIGINT.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Time drawing gets to stop on its own after a signal before the process is ended anyway.
+pub const HARD_EXIT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Flag which tells the executor to lift the pen and return, set when the process is asked to end.
+#[derive(Clone, Debug, Default)]
+pub struct InterruptFlag {
+    interrupted: Arc<AtomicBool>,
+}
+
+impl InterruptFlag {
+    pub fn new() -> InterruptFlag {
+        InterruptFlag::default()
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+
+    pub fn interrupt(&self) {
+        self.interrupted.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Makes SIGINT, SIGTERM and SIGHUP set `flag`, drawing then stops on the main thread,
+/// where releasing the button can't race with a press.
+/// If drawing doesn't stop within `HARD_EXIT_TIMEOUT`, e.g. because a backend hangs,
+/// `release` is called and the process exits. It has to reach the pointer on its own, e.g. through a new backend.
+pub fn interrupt_on_signal<F>(flag: &InterruptFlag, release: F) -> Result<(), Error>
+        where F: Fn() + Send + 'static {
+    let flag = flag.clone();
+    ctrlc::set_handler(move || {
+        flag.interrupt();
+        eprintln!("Interrupted, lifting the pen");
+        thread::sleep(HARD_EXIT_TIMEOUT);
+        release();
+        eprintln!("Drawing didn't stop in time, mouse button released");
+        process::exit(INTERRUPTED_EXIT_CODE);
+    }).map_err(|error| Error::Backend{ message: "Couldn't set signal handler".to_string(), source: Some(Box::new(error)) })
+}
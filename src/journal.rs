@@ -0,0 +1,123 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use error::Error;
+use rect::Rect;
+
+/// Progress of a drawing kept on disk, so that an interrupted run can be resumed.
+/// The file is replaced atomically on every save, so a crash leaves either the old or the new state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Journal {
+    path: PathBuf,
+    fingerprint: u64,
+    paint_area: Rect,
+    completed: usize,
+}
+
+impl Journal {
+    /// Starts a journal for the plan with `fingerprint` drawn into `paint_area`.
+    /// Fails if there's a journal at `path` already, overwriting it would lose the progress of an interrupted run.
+    pub fn create(path: &Path, fingerprint: u64, paint_area: Rect) -> Result<Journal, Error> {
+        if path.exists() {
+            return Err(Error::Journal(format!(
+                "Journal {} of an unfinished run exists, continue it with --resume or delete the journal to start over",
+                path.display())));
+        }
+        let journal = Journal {
+            path: path.to_path_buf(),
+            fingerprint,
+            paint_area,
+            completed: 0,
+        };
+        journal.save()?;
+        Ok(journal)
+    }
+
+    /// Reads the journal an interrupted run left behind.
+    pub fn open(path: &Path) -> Result<Journal, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| Error::io(format!("Couldn't read journal {}", path.display()), error))?;
+        let error = || Error::Journal(format!("Journal {} is corrupted", path.display()));
+        let mut fingerprint = None;
+        let mut paint_area = None;
+        let mut completed = None;
+        for line in content.lines() {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.collect::<Vec<&str>>().as_slice()) {
+                (Some("fingerprint"), &[value]) => {
+                    fingerprint = Some(u64::from_str_radix(value, 16).map_err(|_| error())?);
+                }
+                (Some("area"), &[x, y, width, height]) => {
+                    let numbers = [x, y, width, height].iter()
+                        .map(|number| number.parse::<f32>().map_err(|_| error()))
+                        .collect::<Result<Vec<f32>, Error>>()?;
+                    paint_area = Some(Rect::new(numbers[0], numbers[1], numbers[2], numbers[3]));
+                }
+                (Some("completed"), &[value]) => {
+                    completed = Some(value.parse::<usize>().map_err(|_| error())?);
+                }
+                (None, _) => {}
+                _ => return Err(error()),
+            }
+        }
+        match (fingerprint, paint_area, completed) {
+            (Some(fingerprint), Some(paint_area), Some(completed)) => Ok(Journal {
+                path: path.to_path_buf(),
+                fingerprint,
+                paint_area,
+                completed,
+            }),
+            _ => Err(error()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Fingerprint of the stroke plan being drawn, see `StrokePlan::fingerprint`.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Calibrated area the plan is drawn into.
+    pub fn paint_area(&self) -> &Rect {
+        &self.paint_area
+    }
+
+    /// Number of strokes from the start of the plan which are already drawn.
+    pub fn completed(&self) -> usize {
+        self.completed
+    }
+
+    pub fn set_completed(&mut self, completed: usize) -> Result<(), Error> {
+        self.completed = completed;
+        self.save()
+    }
+
+    /// Deletes the journal once the drawing is done.
+    pub fn remove(self) -> Result<(), Error> {
+        fs::remove_file(&self.path)
+            .map_err(|error| Error::io(format!("Couldn't remove journal {}", self.path.display()), error))
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        self.write().map_err(|error| Error::io(format!("Couldn't write journal {}", self.path.display()), error))
+    }
+
+    fn write(&self) -> io::Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+        {
+            let mut file = fs::File::create(&temporary)?;
+            writeln!(file, "fingerprint {:016x}", self.fingerprint)?;
+            writeln!(file, "area {} {} {} {}", self.paint_area.x(), self.paint_area.y(),
+                     self.paint_area.width(), self.paint_area.height())?;
+            writeln!(file, "completed {}", self.completed)?;
+            file.sync_all()?;
+        }
+        fs::rename(&temporary, &self.path)
+    }
+}
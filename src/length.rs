@@ -0,0 +1,82 @@
+use error::Error;
+
+// CSS pixels per unit, see https://www.w3.org/TR/css-values-3/#absolute-lengths
+const PX_PER_IN: f32 = 96f32;
+const PX_PER_CM: f32 = PX_PER_IN / 2.54f32;
+const PX_PER_MM: f32 = PX_PER_CM / 10f32;
+const PX_PER_PT: f32 = PX_PER_IN / 72f32;
+const PX_PER_PC: f32 = PX_PER_IN / 6f32;
+// Default font size of browsers.
+const PX_PER_EM: f32 = 16f32;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Unit {
+    Px,
+    Pt,
+    Pc,
+    Mm,
+    Cm,
+    In,
+    Em,
+    Percent,
+}
+
+/// SVG length, e.g. "210mm" or "100%".
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    number: f32,
+    unit: Unit,
+}
+
+impl Length {
+    pub fn new(number: f32, unit: Unit) -> Length {
+        Length{ number, unit }
+    }
+
+    pub fn number(&self) -> f32 {
+        self.number
+    }
+
+    pub fn unit(&self) -> Unit {
+        self.unit
+    }
+
+    /// Parses a number optionally followed by a unit, a unitless number is in px.
+    pub fn parse(value: &str) -> Result<Length, Error> {
+        let value = value.trim();
+        let units = [
+            ("%", Unit::Percent),
+            ("px", Unit::Px),
+            ("pt", Unit::Pt),
+            ("pc", Unit::Pc),
+            ("mm", Unit::Mm),
+            ("cm", Unit::Cm),
+            ("in", Unit::In),
+            ("em", Unit::Em),
+        ];
+        let (number, unit) = units.iter()
+            .find(|&&(suffix, _)| value.ends_with(suffix))
+            .map(|&(suffix, unit)| (&value[..value.len() - suffix.len()], unit))
+            .unwrap_or((value, Unit::Px));
+        match number.trim_end().parse::<f32>() {
+            Ok(number) if number.is_finite() => Ok(Length::new(number, unit)),
+            _ => Err(Error::Parse(format!("Couldn't parse length '{}'", value))),
+        }
+    }
+
+    /// Length in px. Percentages are resolved against `percent_base`,
+    /// None is returned for a percentage when there's no base.
+    pub fn to_px(&self, percent_base: Option<f32>) -> Option<f32> {
+        let px_per_unit = match self.unit {
+            Unit::Px => 1f32,
+            Unit::Pt => PX_PER_PT,
+            Unit::Pc => PX_PER_PC,
+            Unit::Mm => PX_PER_MM,
+            Unit::Cm => PX_PER_CM,
+            Unit::In => PX_PER_IN,
+            Unit::Em => PX_PER_EM,
+            Unit::Percent => return percent_base.map(|base| self.number * base / 100f32),
+        };
+        Some(self.number * px_per_unit)
+    }
+}
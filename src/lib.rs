@@ -1,4 +1,33 @@
-pub mod my_error;
+extern crate svg;
+extern crate x11rb;
+extern crate ctrlc;
+
+pub mod error;
 pub mod rect;
 pub mod point;
-pub mod mouse;
\ No newline at end of file
+pub mod mouse;
+pub mod backend;
+pub mod batch_backend;
+pub mod xtest_backend;
+pub mod pen_guard;
+pub mod interrupt;
+pub mod journal;
+pub mod pause;
+pub mod profiles;
+pub mod cli;
+pub mod recording_backend;
+pub mod arc;
+pub mod flatten;
+pub mod painter;
+pub mod document;
+pub mod stroke_plan;
+pub mod executor;
+pub mod pacing;
+pub mod optimize;
+pub mod merge;
+pub mod simplify;
+pub mod fit;
+pub mod length;
+pub mod size;
+pub mod shapes;
+pub mod transform;
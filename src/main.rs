@@ -1,52 +1,373 @@
 extern crate svg;
+extern crate svg_bot;
 
-use svg::node::element::path::Data;
-use svg::parser::Event;
+use svg::parser::{Event, Parser};
+use std::fs;
+use std::io::{self, Write};
 use std::time::Duration;
 use std::thread;
+use std::path::{Path, PathBuf};
+use std::process;
 
-mod point;
-mod rect;
-mod mouse;
-mod my_error;
-mod painter;
+use svg_bot::rect::Rect;
+use svg_bot::document;
+use svg_bot::size;
+use svg_bot::executor::{Executor, Outcome};
+use svg_bot::stroke_plan::StrokePlan;
+use svg_bot::optimize;
+use svg_bot::merge;
+use svg_bot::simplify;
+use svg_bot::pacing::Pacing;
+use svg_bot::fit::{self, PreserveAspectRatio};
+use svg_bot::backend::{PointerBackend, XdotoolBackend};
+use svg_bot::batch_backend::BatchingXdotoolBackend;
+use svg_bot::recording_backend::{PointerEventKind, RecordingBackend};
+use svg_bot::xtest_backend::XTestBackend;
+use svg_bot::pen_guard::PenGuard;
+use svg_bot::{interrupt, mouse};
+use svg_bot::interrupt::InterruptFlag;
+use svg_bot::journal::Journal;
+use svg_bot::pause::PauseSwitch;
+use svg_bot::profiles::Profiles;
+use svg_bot::cli::{self, AreaOptions, BackendKind, Command, PlanOptions, RunOptions};
+use svg_bot::error::{self, Error};
 
-use rect::Rect;
-use painter::Painter;
+const EXIT_FAILURE: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_STOPPED: i32 = 3;
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let path = &args[1];
-    println!("path: {}", path);
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("error: {}", error::chain(&error));
+            process::exit(EXIT_USAGE);
+        }
+    };
+    // Exiting only here makes sure everything, the pen guard in particular, is dropped before.
+    match run(command) {
+        Ok(code) => process::exit(code),
+        Err(error) => {
+            eprintln!("error: {}", error::chain(&error));
+            let code = match error {
+                Error::Usage(_) => EXIT_USAGE,
+                _ => EXIT_FAILURE,
+            };
+            process::exit(code);
+        }
+    }
+}
+
+fn run(command: Command) -> Result<i32, Error> {
+    match command {
+        Command::Help(command) => {
+            print(&cli::usage(command.as_deref())?)?;
+        }
+        Command::Calibrate{ name, countdown, profiles_path } => {
+            calibrate(&name, countdown, profiles_path)?;
+        }
+        Command::Draw(plan_options, run_options) => {
+            return draw(&plan_options, &run_options);
+        }
+        Command::Preview(plan_options, output) => {
+            let svg_area = get_svg_area(&plan_options.svg)?;
+            let paint_area = paint_area(&plan_options.area, &svg_area, false)?;
+            let plan = build_plan(&plan_options, &svg_area, &paint_area)?;
+            write_output(output, &plan.to_svg(&paint_area))?;
+        }
+        Command::Info(plan_options) => {
+            let svg_area = get_svg_area(&plan_options.svg)?;
+            let paint_area = paint_area(&plan_options.area, &svg_area, false)?;
+            let plan = build_plan(&plan_options, &svg_area, &paint_area)?;
+            print_info(&svg_area, &paint_area, &plan)?;
+        }
+        Command::Record(plan_options, pacing) => {
+            let svg_area = get_svg_area(&plan_options.svg)?;
+            let paint_area = paint_area(&plan_options.area, &svg_area, false)?;
+            let plan = build_plan(&plan_options, &svg_area, &paint_area)?;
+            print_events(&plan, pacing)?;
+        }
+        Command::Export(plan_options, output) => {
+            let svg_area = get_svg_area(&plan_options.svg)?;
+            let paint_area = paint_area(&plan_options.area, &svg_area, false)?;
+            let plan = build_plan(&plan_options, &svg_area, &paint_area)?;
+            write_output(output, &plan.to_json())?;
+        }
+    }
+    Ok(0)
+}
 
-    let (top_left_x, top_left_y) = ask_screen_coord("Top left");
-    let (bottom_right_x, bottom_right_y) = ask_screen_coord("Bottom right");
-    let paint_area = Rect::new(top_left_x, top_left_y, bottom_right_x - top_left_x, bottom_right_y - top_left_y);
+fn draw(plan_options: &PlanOptions, run_options: &RunOptions) -> Result<i32, Error> {
+    let path = &plan_options.svg;
+    let svg_area = get_svg_area(path)?;
 
-    assert!(top_left_x < bottom_right_x);
-    assert!(top_left_y < bottom_right_y);
+    if run_options.dry_run {
+        // Nothing may touch the mouse, so the area can't be captured.
+        let paint_area = paint_area(&plan_options.area, &svg_area, false)?;
+        print(&build_plan(plan_options, &svg_area, &paint_area)?.to_json())?;
+        return Ok(0);
+    }
 
-    let svg_area = get_svg_area(path);
+    println!("path: {}", path);
+    let journal_path = run_options.journal.clone().unwrap_or_else(|| format!("{}.journal", path));
+    let resumed = if run_options.resume { Some(Journal::open(Path::new(&journal_path))?) } else { None };
+
+    // A resumed run draws into the area calibrated for the interrupted one.
+    let paint_area = match resumed {
+        Some(ref journal) => journal.paint_area().clone(),
+        None => paint_area(&plan_options.area, &svg_area, true)?,
+    };
     println!("SVG's width: {}, height: {}", svg_area.width(), svg_area.height());
-    
-    for event in svg::open(path).unwrap() {
-        match event {
-            Event::Tag(svg::node::element::tag::Path, _, attributes) => {
-                let data = attributes.get("d").unwrap();
-                let data = Data::parse(data).unwrap();
-                let mut painter = Painter::new(svg_area.clone(), paint_area.clone());
-
-                for command in data.iter() {
-                    painter.perform_command(command).unwrap();
+
+    let plan = build_plan(plan_options, &svg_area, &paint_area)?;
+
+    if let Some(ref journal) = resumed {
+        if journal.fingerprint() != plan.fingerprint() {
+            return Err(Error::Journal(format!(
+                "Journal {} was written for another drawing, SVG or options must have changed", journal_path)));
+        }
+        println!("Resuming after {} of {} strokes", journal.completed(), plan.len());
+    }
+    let pause_switch = PauseSwitch::new();
+    pause_switch.listen_to_stdin();
+    println!("Press Enter to pause or resume drawing");
+    let interrupt_flag = InterruptFlag::new();
+    let run = Run {
+        pacing: run_options.pacing,
+        stop_threshold: run_options.stop_threshold,
+        journal_path: PathBuf::from(journal_path),
+        resumed,
+        paint_area,
+        pause_switch,
+        interrupt_flag: interrupt_flag.clone(),
+    };
+
+    match run_options.backend {
+        BackendKind::Xdotool => {
+            interrupt::interrupt_on_signal(&interrupt_flag, || { let _ = mouse::up(); })?;
+            execute(PenGuard::new(XdotoolBackend::new()), &plan, run, Duration::from_millis(0), Duration::from_millis(0))
+        }
+        BackendKind::XdotoolBatch => {
+            interrupt::interrupt_on_signal(&interrupt_flag, || { let _ = mouse::up(); })?;
+            // Position checks and journal saves flush the batch, so they're made less often to keep batches long.
+            execute(PenGuard::new(BatchingXdotoolBackend::new()), &plan, run,
+                    Duration::from_millis(100), Duration::from_secs(1))
+        }
+        BackendKind::XTest => {
+            interrupt::interrupt_on_signal(&interrupt_flag, || {
+                if let Ok(mut backend) = XTestBackend::new() {
+                    let _ = backend.release();
+                    let _ = backend.flush();
                 }
+            })?;
+            execute(PenGuard::new(XTestBackend::new()?), &plan, run, Duration::from_millis(0), Duration::from_millis(0))
+        }
+    }
+}
+
+/// Everything about a run which doesn't depend on the backend.
+struct Run {
+    pacing: Pacing,
+    stop_threshold: Option<f32>,
+    journal_path: PathBuf,
+    // Journal of the interrupted run when resuming, otherwise a new one is created.
+    resumed: Option<Journal>,
+    paint_area: Rect,
+    pause_switch: PauseSwitch,
+    interrupt_flag: InterruptFlag,
+}
+
+/// Returns the exit code. The pen is released in any case once `backend` is dropped.
+/// The journal is created only here, so a backend which fails to start doesn't leave one behind.
+fn execute<B: PointerBackend>(mut backend: PenGuard<B>, plan: &StrokePlan, run: Run,
+                              stop_check_interval: Duration, journal_interval: Duration) -> Result<i32, Error> {
+    let resuming = run.resumed.is_some();
+    let journal = match run.resumed {
+        Some(journal) => journal,
+        None => Journal::create(&run.journal_path, plan.fingerprint(), run.paint_area)?,
+    };
+    let mut executor = Executor::new(&mut backend);
+    executor.set_pacing(run.pacing);
+    executor.set_stop_threshold(run.stop_threshold);
+    executor.set_stop_check_interval(stop_check_interval);
+    executor.set_journal_interval(journal_interval);
+    executor.set_first_stroke(journal.completed());
+    executor.set_journal(journal);
+    executor.set_pause_switch(run.pause_switch);
+    executor.set_interrupt_flag(run.interrupt_flag);
+    let outcome = match executor.execute(plan) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            // A new run failing before any stroke is completed, e.g. without xdotool, leaves nothing to resume.
+            if let Some(journal) = executor.take_journal().filter(|journal| !resuming && journal.completed() == 0) {
+                let _ = journal.remove();
+            }
+            return Err(error);
+        }
+    };
+    match outcome {
+        Outcome::Finished => {
+            if let Some(journal) = executor.take_journal() {
+                journal.remove()?;
             }
-            _ => {}
+            Ok(0)
         }
-        mouse::up().unwrap();
+        Outcome::Stopped(stop) => {
+            println!("Stopped because the mouse was moved: expected it at ({}, {}) but found at ({}, {})",
+                     stop.expected().x(), stop.expected().y(), stop.actual().x(), stop.actual().y());
+            println!("Stroke {} of {} was drawn up to its point {}", stop.stroke() + 1, plan.len(), stop.point());
+            println!("Run again with --resume to continue");
+            Ok(EXIT_STOPPED)
+        }
+        Outcome::Interrupted => {
+            println!("Interrupted, run again with --resume to continue");
+            Ok(interrupt::INTERRUPTED_EXIT_CODE)
+        }
+    }
+}
+
+fn build_plan(options: &PlanOptions, svg_area: &Rect, paint_area: &Rect) -> Result<StrokePlan, Error> {
+    let aspect_ratio = get_preserve_aspect_ratio(&options.svg)?;
+    let svg_to_screen = fit::svg_to_screen(svg_area, paint_area, &aspect_ratio, &options.scale_mode, options.margin)?;
+    let plan = document::compile_with_tolerance(open_svg(&options.svg)?, &svg_to_screen, options.tolerance)?;
+    let simplified = simplify::simplify(&plan, options.simplify);
+    eprintln!("Points: {} -> {} after simplification", point_count(&plan), point_count(&simplified));
+    if options.keep_order {
+        return Ok(simplified);
     }
+    Ok(optimize_plan(&simplified, options.merge_tolerance))
 }
 
-fn ask_screen_coord(location_name: &str) -> (f32, f32) {
+fn point_count(plan: &StrokePlan) -> usize {
+    plan.strokes().iter().map(|stroke| stroke.points().len()).sum()
+}
+
+// Reports go to stderr to keep the output of export and preview clean.
+fn optimize_plan(plan: &StrokePlan, merge_tolerance: f32) -> StrokePlan {
+    let merged = merge::merge(plan, merge_tolerance);
+    eprintln!("Strokes: {} -> {} after merging", plan.len(), merged.len());
+    let (optimized, report) = optimize::optimize(&merged);
+    eprintln!("Pen-up travel: {:.0}px -> {:.0}px, saved {:.0}px",
+              report.travel_before(), report.travel_after(), report.saved());
+    optimized
+}
+
+fn write_output(output: Option<String>, content: &str) -> Result<(), Error> {
+    match output {
+        Some(path) => fs::write(&path, content)
+            .map_err(|error| Error::io(format!("Couldn't write {}", path), error)),
+        None => print(content),
+    }
+}
+
+/// Prints `text` and a newline to stdout. A closed stdout isn't an error,
+/// the output is cut short like with `svg-bot export image.svg | head -1`.
+fn print(text: &str) -> Result<(), Error> {
+    let mut stdout = io::stdout();
+    match stdout.write_all(text.as_bytes()).and_then(|_| stdout.write_all(b"\n")).and_then(|_| stdout.flush()) {
+        Err(ref error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.map_err(|error| Error::io("Couldn't write to stdout".to_string(), error)),
+    }
+}
+
+fn print_info(svg_area: &Rect, paint_area: &Rect, plan: &StrokePlan) -> Result<(), Error> {
+    let pen_down: f32 = plan.strokes().iter()
+        .map(|stroke| stroke.points().windows(2).map(|pair| pair[0].distance_to(&pair[1])).sum::<f32>())
+        .sum();
+    print(&[
+        format!("SVG area: {} {} {} {}", svg_area.x(), svg_area.y(), svg_area.width(), svg_area.height()),
+        format!("Paint area: {} {} {} {}", paint_area.x(), paint_area.y(), paint_area.width(), paint_area.height()),
+        format!("Strokes: {}", plan.len()),
+        format!("Points: {}", point_count(plan)),
+        format!("Pen-down distance: {:.0}px", pen_down),
+        format!("Pen-up travel: {:.0}px", optimize::travel_distance(plan)),
+    ].join("\n"))
+}
+
+fn print_events(plan: &StrokePlan, pacing: Pacing) -> Result<(), Error> {
+    let mut backend = RecordingBackend::new();
+    {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_pacing(pacing);
+        executor.execute(plan)?;
+    }
+    let lines = backend.events().iter().map(|event| {
+        let millis = event.time().as_secs_f64() * 1000f64;
+        match *event.kind() {
+            PointerEventKind::Press => format!("{:.1} press", millis),
+            PointerEventKind::Release => format!("{:.1} release", millis),
+            PointerEventKind::Move(x, y) => format!("{:.1} move {} {}", millis, x, y),
+        }
+    }).collect::<Vec<_>>();
+    print(&lines.join("\n"))
+}
+
+/// Captures the paint area and saves it as a profile which `--profile <name>` draws into.
+fn calibrate(name: &str, countdown: bool, profiles_path: Option<String>) -> Result<(), Error> {
+    let profiles_path = profiles_path_or_default(profiles_path)?;
+    let mut profiles = Profiles::load(&profiles_path)?;
+    let area = capture_area(countdown)?;
+    profiles.set(name, area.clone())?;
+    profiles.save(&profiles_path)?;
+    println!("Saved profile '{}': {},{},{},{} to {}",
+             name, area.x(), area.y(), area.width(), area.height(), profiles_path.display());
+    Ok(())
+}
+
+fn profiles_path_or_default(profiles_path: Option<String>) -> Result<PathBuf, Error> {
+    match profiles_path {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => Profiles::default_path()
+            .ok_or_else(|| Error::Calibration("Couldn't find config directory, set HOME or use --profiles".to_string())),
+    }
+}
+
+/// Paint area from `--area`, a profile, or captured from the mouse if `interactive`.
+/// Commands which don't touch the mouse paint the SVG in its own size at the top left corner of the screen.
+fn paint_area(options: &AreaOptions, svg_area: &Rect, interactive: bool) -> Result<Rect, Error> {
+    if let Some(ref area) = options.area {
+        return Ok(area.clone());
+    }
+    if let Some(ref profile) = options.profile {
+        let profiles_path = profiles_path_or_default(options.profiles_path.clone())?;
+        let profiles = Profiles::load(&profiles_path)?;
+        return match profiles.get(profile) {
+            Some(area) => Ok(area.clone()),
+            None => Err(Error::Calibration(format!("No profile '{}' in {}, known profiles: {}",
+                                             profile, profiles_path.display(), profiles.names().join(", ")))),
+        };
+    }
+    if interactive || options.countdown {
+        return capture_area(options.countdown);
+    }
+    Ok(Rect::new(0f32, 0f32, svg_area.width(), svg_area.height()))
+}
+
+/// Reads the paint area from the pointer put on its corners,
+/// either after a countdown or once Enter is pressed.
+fn capture_area(countdown: bool) -> Result<Rect, Error> {
+    let capture = if countdown { ask_screen_coord } else { wait_for_screen_coord };
+    let (top_left_x, top_left_y) = capture("Top left")?;
+    let (bottom_right_x, bottom_right_y) = capture("Bottom right")?;
+
+    if top_left_x >= bottom_right_x || top_left_y >= bottom_right_y {
+        return Err(Error::Calibration(format!(
+            "Bottom right corner ({}, {}) must be below and to the right of top left corner ({}, {})",
+            bottom_right_x, bottom_right_y, top_left_x, top_left_y)));
+    }
+
+    Ok(Rect::new(top_left_x, top_left_y, bottom_right_x - top_left_x, bottom_right_y - top_left_y))
+}
+
+fn wait_for_screen_coord(location_name: &str) -> Result<(f32, f32), Error> {
+    println!("Move the mouse to the {} location and press Enter", location_name.to_lowercase());
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+
+    pointer_position()
+}
+
+fn ask_screen_coord(location_name: &str) -> Result<(f32, f32), Error> {
     println!("{} location will be read in:", location_name);
     println!("3");
     thread::sleep(Duration::from_millis(1000));
@@ -54,47 +375,47 @@ fn ask_screen_coord(location_name: &str) -> (f32, f32) {
     thread::sleep(Duration::from_millis(1000));
     println!("1");
     thread::sleep(Duration::from_millis(1000));
-    
-    mouse::coords().unwrap()
+
+    pointer_position()
 }
 
-fn get_svg_area(path_to_svg: &str) -> Rect {
-     for event in svg::open(path_to_svg).unwrap() {
-        match event {
-            Event::Tag(svg::node::element::tag::SVG, _, attributes) => {
-                let view_box = attributes.get("viewBox");
-                if view_box.is_none() {
-                    continue;
-                }
-                let view_box = view_box.unwrap().split(" ");
-                let view_box = view_box.collect::<Vec<_>>();
-                let x =  view_box[0].parse::<f32>().unwrap();
-                let y =  view_box[1].parse::<f32>().unwrap();
-                let width = view_box[2].parse::<f32>().unwrap();
-                let height = view_box[3].parse::<f32>().unwrap();
-                return Rect::new(x, y, width, height);
-            }
-            _ => {}
+// XTest doesn't need the xdotool binary, xdotool works where the XTEST extension is missing.
+fn pointer_position() -> Result<(f32, f32), Error> {
+    match XTestBackend::new() {
+        Ok(mut backend) => backend.position(),
+        Err(_) => XdotoolBackend::new().position(),
+    }
+}
+
+fn open_svg(path_to_svg: &str) -> Result<Parser<'static>, Error> {
+    svg::open(path_to_svg).map_err(|error| Error::io(format!("Couldn't open {}", path_to_svg), error))
+}
+
+fn get_preserve_aspect_ratio(path_to_svg: &str) -> Result<PreserveAspectRatio, Error> {
+    for event in open_svg(path_to_svg)? {
+        if let Event::Tag(svg::node::element::tag::SVG, _, attributes) = event {
+            return match attributes.get("preserveAspectRatio") {
+                Some(value) => PreserveAspectRatio::parse(value),
+                None => Ok(PreserveAspectRatio::default()),
+            };
         }
     }
+    Ok(PreserveAspectRatio::default())
+}
 
-    for event in svg::open(path_to_svg).unwrap() {
-        match event {
-            Event::Tag(svg::node::element::tag::SVG, _, attributes) => {
-                let width = attributes.get("width");
-                let height = attributes.get("height");
-                if width.is_none() || height.is_none() {
-                    continue;
-                }
-                match (width, height) {
-                    (Some(width), Some(height)) => {
-                        return Rect::new(0f32, 0f32, width.parse::<f32>().unwrap(), height.parse::<f32>().unwrap());
-                    }
-                    (_, _) => {}
-                }
+fn get_svg_area(path_to_svg: &str) -> Result<Rect, Error> {
+    for event in open_svg(path_to_svg)? {
+        if let Event::Tag(svg::node::element::tag::SVG, _, attributes) = event {
+            if let Some(area) = size::root_area(&attributes)? {
+                return Ok(area);
             }
-            _ => {}
+            break;
         }
     }
-    panic!("Couldn't find size of SVG!");
+
+    eprintln!("SVG doesn't declare its size, using bounding box of its content");
+    match document::content_bounds(open_svg(path_to_svg)?)? {
+        Some(area) => Ok(area),
+        None => Err(Error::Unsupported(format!("Couldn't find size of {}: it declares none and draws nothing", path_to_svg))),
+    }
 }
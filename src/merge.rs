@@ -0,0 +1,85 @@
+use point::ScreenPoint;
+use stroke_plan::{Stroke, StrokePlan};
+
+/// Default distance in screen pixels below which stroke endpoints are considered the same point.
+pub const DEFAULT_MERGE_TOLERANCE: f32 = 1f32;
+
+/// Joins strokes whose endpoints are no further than `tolerance` pixels apart into continuous strokes,
+/// reversing them where needed. Only strokes of the same color are joined.
+/// A joined stroke keeps the element id only if all of its parts came from the same element.
+/// Chains start at the earliest stroke not joined yet, so the plan's order is kept as far as possible.
+pub fn merge(plan: &StrokePlan, tolerance: f32) -> StrokePlan {
+    let mut remaining: Vec<Option<&Stroke>> = plan.strokes().iter()
+        .filter(|stroke| !stroke.points().is_empty())
+        .map(Some)
+        .collect();
+    let mut merged = StrokePlan::default();
+    for i in 0..remaining.len() {
+        let first = match remaining[i].take() {
+            Some(stroke) => stroke,
+            None => continue,
+        };
+        let mut points = first.points().to_vec();
+        let mut element_id = first.element_id();
+        while let Some((j, reverse, append)) = find_neighbour(&remaining, &points, first.color(), tolerance) {
+            let stroke = remaining[j].take().unwrap();
+            let mut next = stroke.points().to_vec();
+            if reverse {
+                next.reverse();
+            }
+            if append {
+                join(&mut points, next);
+            } else {
+                join(&mut next, points);
+                points = next;
+            }
+            if element_id != stroke.element_id() {
+                element_id = None;
+            }
+        }
+        merged.push(Stroke::new(points, element_id.map(String::from), first.color().map(String::from)));
+    }
+    merged
+}
+
+/// Finds a stroke which continues the chain: its index,
+/// whether it must be reversed, and whether it goes after the chain's end or before its start.
+fn find_neighbour(remaining: &[Option<&Stroke>], chain: &[ScreenPoint],
+                  color: Option<&str>, tolerance: f32) -> Option<(usize, bool, bool)> {
+    let chain_start = &chain[0];
+    let chain_end = &chain[chain.len() - 1];
+    // A closed chain has nowhere to continue.
+    if chain.len() > 1 && chain_start.distance_to(chain_end) <= tolerance {
+        return None;
+    }
+    for (i, stroke) in remaining.iter().enumerate() {
+        let stroke = match *stroke {
+            Some(stroke) if stroke.color() == color => stroke,
+            _ => continue,
+        };
+        let start = &stroke.points()[0];
+        let end = &stroke.points()[stroke.points().len() - 1];
+        if chain_end.distance_to(start) <= tolerance {
+            return Some((i, false, true));
+        }
+        if chain_end.distance_to(end) <= tolerance {
+            return Some((i, true, true));
+        }
+        if chain_start.distance_to(end) <= tolerance {
+            return Some((i, false, false));
+        }
+        if chain_start.distance_to(start) <= tolerance {
+            return Some((i, true, false));
+        }
+    }
+    None
+}
+
+/// Appends `next` to `points`. The joint is kept as a short segment unless both ends are exactly the same point.
+fn join(points: &mut Vec<ScreenPoint>, next: Vec<ScreenPoint>) {
+    let mut next = next.into_iter().peekable();
+    if points.last() == next.peek() {
+        next.next();
+    }
+    points.extend(next);
+}
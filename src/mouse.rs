@@ -1,27 +1,28 @@
 use std::process::Command;
 use std::str::from_utf8;
 
-use my_error::MyError;
+use error::Error;
 
-pub fn coords() -> Result<(f32, f32), MyError> {
-    let output = Command::new("xdotool").arg("getmouselocation").output()?;
+pub fn coords() -> Result<(f32, f32), Error> {
+    let output = Command::new("xdotool").arg("getmouselocation").output()
+        .map_err(|error| Error::spawn("xdotool getmouselocation", error))?;
     if !output.status.success() {
-        return Err(MyError::new("xdotool getmouselocation finished with failure".to_string()));
+        return Err(Error::backend("xdotool getmouselocation finished with failure".to_string()));
     }
 
-    let output = from_utf8(&output.stdout)?;
+    let output = from_utf8(&output.stdout).map_err(|error| Error::Backend {
+        message: "xdotool getmouselocation returned invalid data".to_string(),
+        source: Some(Box::new(error)),
+    })?;
+    // The output looks like "x:123 y:456 screen:0 window:789".
     let mut top_left_strs = output.split(" ");
 
-    let x_str = top_left_strs.nth(0);
-    let y_str = top_left_strs.nth(0);
-    if x_str.is_none() || y_str.is_none() {
-        return Err(MyError::new("xdotool getmouselocation returned invalid data: ".to_string() + output));
-    }
-    let x_str = x_str.unwrap();
-    let y_str = y_str.unwrap();
-
-    let x_str = &x_str[2..];
-    let y_str = &y_str[2..];
+    let x_str = top_left_strs.next().and_then(|x_str| x_str.strip_prefix("x:"));
+    let y_str = top_left_strs.next().and_then(|y_str| y_str.strip_prefix("y:"));
+    let (x_str, y_str) = match (x_str, y_str) {
+        (Some(x_str), Some(y_str)) => (x_str, y_str),
+        _ => return Err(Error::backend("xdotool getmouselocation returned invalid data: ".to_string() + output)),
+    };
 
     let x = x_str.parse::<f32>();
     let y = y_str.parse::<f32>();
@@ -31,37 +32,40 @@ pub fn coords() -> Result<(f32, f32), MyError> {
             Ok((x_val, y_val))
         }
         (_, _) => {
-            Err(MyError::new(format!("Couldn't parse coords returned by xdotool getmouselocation: {} {}", x_str, y_str)))
+            Err(Error::backend(format!("Couldn't parse coords returned by xdotool getmouselocation: {} {}", x_str, y_str)))
         }
     }
 }
 
-pub fn down() -> Result<(), MyError> {
-    let status = Command::new("xdotool").arg("mousedown").arg("1").status()?;
+pub fn down() -> Result<(), Error> {
+    let status = Command::new("xdotool").arg("mousedown").arg("1").status()
+        .map_err(|error| Error::spawn("xdotool mousedown", error))?;
     if !status.success() {
-        Err(MyError::new("xdotool mousedown finished with failure".to_string()))
+        Err(Error::backend("xdotool mousedown finished with failure".to_string()))
     } else {
         Ok(())
     }
 }
 
-pub fn up() -> Result<(), MyError> {
-    let status = Command::new("xdotool").arg("mouseup").arg("1").status()?;
+pub fn up() -> Result<(), Error> {
+    let status = Command::new("xdotool").arg("mouseup").arg("1").status()
+        .map_err(|error| Error::spawn("xdotool mouseup", error))?;
     if !status.success() {
-        Err(MyError::new("xdotool mouseup finished with failure".to_string()))
+        Err(Error::backend("xdotool mouseup finished with failure".to_string()))
     } else {
         Ok(())
     }
 }
 
-pub fn move_to(x: f32, y: f32) -> Result<(), MyError> {
+pub fn move_to(x: f32, y: f32) -> Result<(), Error> {
     let status = Command::new("xdotool")
                      .arg("mousemove")
                      .arg(x.to_string())
                      .arg(y.to_string())
-                     .status()?;
+                     .status()
+                     .map_err(|error| Error::spawn("xdotool mousemove", error))?;
     if !status.success() {
-        Err(MyError::new("xdotool mousemove finished with failure".to_string()))
+        Err(Error::backend("xdotool mousemove finished with failure".to_string()))
     } else {
         Ok(())
     }
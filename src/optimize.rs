@@ -0,0 +1,122 @@
+use point::ScreenPoint;
+use stroke_plan::{Stroke, StrokePlan};
+
+// Each 2-opt pass is quadratic in the number of strokes, so huge drawings stop improving early.
+const MAX_TWO_OPT_PASSES: usize = 50;
+
+/// How much pen-up travel the optimizer got rid of, in screen pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimizeReport {
+    travel_before: f32,
+    travel_after: f32,
+}
+
+impl OptimizeReport {
+    pub fn travel_before(&self) -> f32 {
+        self.travel_before
+    }
+
+    pub fn travel_after(&self) -> f32 {
+        self.travel_after
+    }
+
+    pub fn saved(&self) -> f32 {
+        self.travel_before - self.travel_after
+    }
+}
+
+/// Total distance the pen travels lifted between the strokes of the plan.
+pub fn travel_distance(plan: &StrokePlan) -> f32 {
+    let strokes = non_empty(plan.strokes());
+    strokes.windows(2).map(|pair| travel(pair[0], pair[1])).sum()
+}
+
+/// Reorders strokes and reverses their direction to shorten pen-up travel:
+/// nearest neighbour ordering first, then 2-opt improvement.
+/// Empty strokes are dropped. The result never travels more than the original plan.
+pub fn optimize(plan: &StrokePlan) -> (StrokePlan, OptimizeReport) {
+    let travel_before = travel_distance(plan);
+    let mut strokes = nearest_neighbour(non_empty(plan.strokes()));
+    two_opt(&mut strokes);
+    let mut optimized = StrokePlan::new(strokes);
+    let mut travel_after = travel_distance(&optimized);
+    if travel_after > travel_before {
+        optimized = StrokePlan::new(non_empty(plan.strokes()).into_iter().cloned().collect());
+        travel_after = travel_before;
+    }
+    (optimized, OptimizeReport{ travel_before, travel_after })
+}
+
+fn non_empty(strokes: &[Stroke]) -> Vec<&Stroke> {
+    strokes.iter().filter(|stroke| !stroke.points().is_empty()).collect()
+}
+
+fn nearest_neighbour(mut remaining: Vec<&Stroke>) -> Vec<Stroke> {
+    let mut result: Vec<Stroke> = Vec::with_capacity(remaining.len());
+    if remaining.is_empty() {
+        return result;
+    }
+    // The pen position before drawing is unknown, so the first stroke stays where it is.
+    result.push(remaining.remove(0).clone());
+    while !remaining.is_empty() {
+        let position = end(&result[result.len() - 1]).clone();
+        let mut best = (0, false, f32::MAX);
+        for (i, stroke) in remaining.iter().enumerate() {
+            let to_start = position.distance_to(start(stroke));
+            let to_end = position.distance_to(end(stroke));
+            if to_start < best.2 {
+                best = (i, false, to_start);
+            }
+            if to_end < best.2 {
+                best = (i, true, to_end);
+            }
+        }
+        let stroke = remaining.remove(best.0);
+        result.push(if best.1 { stroke.reversed() } else { stroke.clone() });
+    }
+    result
+}
+
+/// Reversing a run of strokes reverses their order as well as each of them,
+/// so only the travels at both ends of the run change.
+fn two_opt(strokes: &mut [Stroke]) {
+    let count = strokes.len();
+    for _ in 0..MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+        for i in 0..count {
+            for j in i..count {
+                let mut delta = 0f32;
+                if i > 0 {
+                    let before = end(&strokes[i - 1]);
+                    delta += before.distance_to(end(&strokes[j])) - before.distance_to(start(&strokes[i]));
+                }
+                if j + 1 < count {
+                    let after = start(&strokes[j + 1]);
+                    delta += start(&strokes[i]).distance_to(after) - end(&strokes[j]).distance_to(after);
+                }
+                if delta < -1e-3 {
+                    strokes[i..=j].reverse();
+                    for stroke in &mut strokes[i..=j] {
+                        *stroke = stroke.reversed();
+                    }
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+fn travel(from: &Stroke, to: &Stroke) -> f32 {
+    end(from).distance_to(start(to))
+}
+
+fn start(stroke: &Stroke) -> &ScreenPoint {
+    &stroke.points()[0]
+}
+
+fn end(stroke: &Stroke) -> &ScreenPoint {
+    &stroke.points()[stroke.points().len() - 1]
+}
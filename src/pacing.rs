@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use error::Error;
+
+/// Named pacings for common target programs.
+pub const PRESETS: &[&str] = &["instant", "fast", "paint", "browser", "careful"];
+
+/// How fast strokes are drawn.
+/// Many paint programs drop or straighten pointer moves which come too fast or too far apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pacing {
+    move_delay: Duration,
+    press_delay: Duration,
+    release_delay: Duration,
+    max_step: Option<f32>,
+}
+
+impl Pacing {
+    pub fn new(move_delay: Duration, press_delay: Duration, release_delay: Duration,
+               max_step: Option<f32>) -> Pacing {
+        Pacing{ move_delay, press_delay, release_delay, max_step }
+    }
+
+    /// Pause after each move.
+    pub fn move_delay(&self) -> Duration {
+        self.move_delay
+    }
+
+    /// Pause before and after pressing the button.
+    pub fn press_delay(&self) -> Duration {
+        self.press_delay
+    }
+
+    /// Pause before and after releasing the button.
+    pub fn release_delay(&self) -> Duration {
+        self.release_delay
+    }
+
+    /// Longest move in pixels made while the button is pressed, longer segments are split into steps.
+    pub fn max_step(&self) -> Option<f32> {
+        self.max_step
+    }
+
+    pub fn with_move_delay(self, move_delay: Duration) -> Pacing {
+        Pacing{ move_delay, ..self }
+    }
+
+    pub fn with_press_delay(self, press_delay: Duration) -> Pacing {
+        Pacing{ press_delay, ..self }
+    }
+
+    pub fn with_release_delay(self, release_delay: Duration) -> Pacing {
+        Pacing{ release_delay, ..self }
+    }
+
+    pub fn with_max_step(self, max_step: Option<f32>) -> Pacing {
+        Pacing{ max_step, ..self }
+    }
+
+    /// Looks up one of `PRESETS`:
+    /// - instant: no pauses at all;
+    /// - fast: programs which handle every event, e.g. Inkscape;
+    /// - paint: MS Paint, GIMP, Krita and the like;
+    /// - browser: canvases in web pages, which sample the pointer once a frame;
+    /// - careful: slow remote desktops and everything else which loses events.
+    pub fn preset(name: &str) -> Result<Pacing, Error> {
+        let (move_delay, press_delay, release_delay, max_step) = match name {
+            "instant" => (0, 0, 0, None),
+            "fast" => (1, 10, 10, Some(20f32)),
+            "paint" => (4, 30, 30, Some(8f32)),
+            "browser" => (17, 50, 50, Some(10f32)),
+            "careful" => (25, 100, 100, Some(4f32)),
+            _ => return Err(Error::Parse(format!(
+                "Unknown speed preset '{}', expected one of {}", name, PRESETS.join(", ")))),
+        };
+        Ok(Pacing::new(Duration::from_millis(move_delay),
+                       Duration::from_millis(press_delay),
+                       Duration::from_millis(release_delay),
+                       max_step))
+    }
+}
+
+impl Default for Pacing {
+    /// As fast as possible, like the bot always used to draw.
+    fn default() -> Self {
+        Pacing::preset("instant").unwrap()
+    }
+}
@@ -1,104 +1,134 @@
-use svg::node::element::path::{Command, Position, Parameters};
-use rect::Rect;
+use svg::node::element::path::{Command, Position};
 use point::{SvgPoint, ScreenPoint};
-use my_error::MyError;
-use mouse;
+use arc::Arc;
+use flatten::{flatten, DEFAULT_TOLERANCE};
+use transform::Transform;
+use error::Error;
 
+/// Kind of the curve command which produced the last control point.
+/// Smooth curve commands reflect the last control point only if it came from a curve of the same kind.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CurveKind {
+    Quadratic,
+    Cubic,
+}
+
+/// Turns path commands into polylines in screen coordinates.
 pub struct Painter {
     current_point: SvgPoint,
     subpath_initial_point: Option<SvgPoint>,
-    svg_area: Rect,
-    screen_area: Rect,
+    last_control_point: Option<(CurveKind, SvgPoint)>,
+    tolerance: f32,
+    // Maps path's user space onto the screen.
+    transform: Transform,
+    polylines: Vec<Vec<ScreenPoint>>,
+    // Whether the last polyline is still being drawn, i.e. there was no move since it started.
+    pen_down: bool,
 }
 
 impl Painter {
-    pub fn new(svg_area: Rect, screen_area: Rect) -> Painter {
+    /// `transform` must map path's user space onto the screen.
+    pub fn new(transform: Transform) -> Painter {
         Painter {
-            current_point: SvgPoint::new(0f32, 0f32, &svg_area, &screen_area),
+            current_point: SvgPoint::new(0f32, 0f32),
             subpath_initial_point: None,
-            svg_area: svg_area,
-            screen_area: screen_area
+            last_control_point: None,
+            tolerance: DEFAULT_TOLERANCE,
+            transform,
+            polylines: Vec::new(),
+            pen_down: false,
         }
     }
 
-    pub fn perform_command(&mut self, command: &Command) -> Result<(), MyError> {
+    /// Polylines drawn by the performed commands, in the order they were drawn.
+    pub fn into_polylines(self) -> Vec<Vec<ScreenPoint>> {
+        self.polylines
+    }
+
+    /// Sets maximal distance in screen pixels between a curve and the lines it's drawn with.
+    pub fn set_tolerance(&mut self, tolerance: f32) {
+        self.tolerance = tolerance;
+    }
+
+    pub fn perform_command(&mut self, command: &Command) -> Result<(), Error> {
+        let previous_control_point = self.last_control_point.take();
         match command {
-            &Command::Move(ref position_type, ref params) => {
-                let params = params.to_vec();
-                self.reset_subpath_initial_point_by_move_command(&position_type, &params);
-                self.perform_move(&position_type, &params.to_vec())?;
+            Command::Move(position_type, params) => {
+                self.reset_subpath_initial_point_by_move_command(position_type, params)?;
+                self.perform_move(position_type, params)?;
             },
-            &Command::Line(ref position_type, ref params) => {
+            Command::Line(position_type, params) => {
                 self.validate_subpath_initial_point();
-                self.perform_line(&position_type, &params.to_vec())?;
+                self.perform_line(position_type, params)?;
             },
-            &Command::CubicCurve(ref position_type, ref params) => {
+            Command::CubicCurve(position_type, params) => {
                 self.validate_subpath_initial_point();
-                self.perform_cubic_curve(&position_type, &params.to_vec())?;
+                self.perform_cubic_curve(position_type, params)?;
             },
-            &Command::Close => {
+            Command::Close => {
                 self.validate_subpath_initial_point();
                 self.perform_close()?;
             },
-            &Command::HorizontalLine(ref position_type, ref params) => {
+            Command::HorizontalLine(position_type, params) => {
                 self.validate_subpath_initial_point();
-                self.perform_horizontal_line(&position_type, &params.to_vec())?;
+                self.perform_horizontal_line(position_type, params)?;
             },
-            &Command::VerticalLine(ref position_type, ref params) => {
+            Command::VerticalLine(position_type, params) => {
                 self.validate_subpath_initial_point();
-                self.perform_vertical_line(&position_type, &params.to_vec())?;
+                self.perform_vertical_line(position_type, params)?;
             },
-            &Command::QuadraticCurve(_, ref params) => {
+            Command::QuadraticCurve(position_type, params) => {
                 self.validate_subpath_initial_point();
-                print_params("QuadraticCurve", &params);
+                self.perform_quadratic_curve(position_type, params)?;
             },
-            &Command::SmoothQuadraticCurve(_, ref params) => {
+            Command::SmoothQuadraticCurve(position_type, params) => {
                 self.validate_subpath_initial_point();
-                print_params("SmoothQuadraticCurve", &params);
+                self.perform_smooth_quadratic_curve(position_type, params, previous_control_point)?;
             },
-            &Command::SmoothCubicCurve(_, ref params) => {
+            Command::SmoothCubicCurve(position_type, params) => {
                 self.validate_subpath_initial_point();
-                print_params("SmoothCubicCurve", &params);
+                self.perform_smooth_cubic_curve(position_type, params, previous_control_point)?;
             },
-            &Command::EllipticalArc(_, ref params) => {
+            Command::EllipticalArc(position_type, params) => {
                 self.validate_subpath_initial_point();
-                print_params("EllipticalArc", &params);
+                self.perform_elliptical_arc(position_type, params)?;
             },
         }
         Ok(())
     }
 
-    fn reset_subpath_initial_point_by_move_command(&mut self, move_position_type: &Position, move_params: &Vec<f32>) {
-        assert!(move_params.len() >= 2);
+    fn reset_subpath_initial_point_by_move_command(&mut self, move_position_type: &Position, move_params: &[f32]) -> Result<(), Error> {
+        check_pairs("Move", move_params)?;
 
         let new_initial_x: f32;
         let new_initial_y: f32;
-        match move_position_type {
-            &Position::Absolute => {
-                new_initial_x = *move_params.get(0).unwrap();
-                new_initial_y = *move_params.get(1).unwrap();
+        match *move_position_type {
+            Position::Absolute => {
+                new_initial_x = move_params[0];
+                new_initial_y = move_params[1];
             }
-            &Position::Relative => {
-                new_initial_x = *move_params.get(0).unwrap() + self.current_point.x();
-                new_initial_y = *move_params.get(1).unwrap() + self.current_point.y();
+            Position::Relative => {
+                new_initial_x = move_params[0] + self.current_point.x();
+                new_initial_y = move_params[1] + self.current_point.y();
             }
         }
-        self.subpath_initial_point = Some(SvgPoint::new(new_initial_x, new_initial_y, &self.svg_area, &self.screen_area));
+        self.subpath_initial_point = Some(SvgPoint::new(new_initial_x, new_initial_y));
+        Ok(())
     }
 
-    fn perform_move(&mut self, position_type: &Position, params: &Vec<f32>) -> Result<(), MyError> {
-        assert!(params.len() >= 2);
-        mouse::up()?;
+    fn perform_move(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        check_pairs("Move", params)?;
+        self.pen_down = false;
 
-        self.current_point = SvgPoint::new(*params.get(0).unwrap(), *params.get(1).unwrap(), &self.svg_area, &self.screen_area);
+        self.current_point = match *position_type {
+            Position::Absolute => SvgPoint::new(params[0], params[1]),
+            Position::Relative => self.current_point.clone().offset(params[0], params[1]),
+        };
         if params.len() > 2 {
             // If move has more than 2 points than they must be treated as implicit line.
-            let mut params = params.clone();
-            params.remove(0);
-            params.remove(0);
-            return self.perform_line(position_type, &params)
+            self.perform_line(position_type, &params[2..])
         } else {
-            return Ok(());
+            Ok(())
         }
     }
 
@@ -108,84 +138,168 @@ impl Painter {
         }
     }
 
-    fn perform_line(&mut self, position_type: &Position, params: &Vec<f32>) -> Result<(), MyError> {
-        assert!(params.len() >= 2);
-        assert_eq!(params.len() % 2, 0);
-
-        let svg_area = &self.svg_area;
-        let screen_area = &self.screen_area;
+    fn perform_line(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        check_pairs("Line", params)?;
 
         let mut current_point = self.current_point.clone();
-        let current_screen_point = ScreenPoint::from(current_point.clone());
-        mouse::move_to(current_screen_point.x(), current_screen_point.y())?;
-        mouse::down()?;
-
-        let mut index = 0;
-        while index < params.len() {
-            let x = *params.get(index).unwrap();
-            let y = *params.get(index + 1).unwrap();
-            match position_type {
-                &Position::Relative => {
+        let current_screen_point = self.to_screen(&current_point);
+        if self.pen_down {
+            self.push_screen_point(current_screen_point);
+        } else {
+            self.polylines.push(vec![current_screen_point]);
+            self.pen_down = true;
+        }
+
+        for coords in params.chunks(2) {
+            let (x, y) = (coords[0], coords[1]);
+            match *position_type {
+                Position::Relative => {
                     current_point = current_point.offset(x, y);
                 }
-                &Position::Absolute => {
-                    current_point = SvgPoint::new(x, y, svg_area, screen_area);
+                Position::Absolute => {
+                    current_point = SvgPoint::new(x, y);
                 }
             }
-            let current_screen_point = ScreenPoint::from(current_point.clone());
-            mouse::move_to(current_screen_point.x(), current_screen_point.y())?;
-            index += 2;
+            let current_screen_point = self.to_screen(&current_point);
+            self.push_screen_point(current_screen_point);
         }
 
         self.current_point = current_point;
         Ok(())
     }
 
-    fn perform_cubic_curve(&mut self, position_type: &Position, params: &Vec<f32>) -> Result<(), MyError> {
-        assert!(params.len() >= 6);
+    // Consecutive duplicates are skipped, they don't change the drawing.
+    fn push_screen_point(&mut self, point: ScreenPoint) {
+        let polyline = self.polylines.last_mut().unwrap();
+        if polyline.last() != Some(&point) {
+            polyline.push(point);
+        }
+    }
+
+    fn perform_cubic_curve(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        if params.is_empty() || !params.len().is_multiple_of(6) {
+            return Err(Error::path_data(format!("Cubic curve expects groups of 6 params, got {}", params.len())));
+        }
 
         let mut line_coords: Vec<f32> = Vec::new();
-        {
-            let pos0 = &self.current_point;
-            let pos1: SvgPoint;
-            let pos2: SvgPoint;
-            let pos3: SvgPoint;
-            match position_type {
-                &Position::Absolute => {
-                    pos1 = SvgPoint::new(*params.get(0).unwrap(), *params.get(1).unwrap(), &self.svg_area, &self.screen_area);
-                    pos2 = SvgPoint::new(*params.get(2).unwrap(), *params.get(3).unwrap(), &self.svg_area, &self.screen_area);
-                    pos3 = SvgPoint::new(*params.get(4).unwrap(), *params.get(5).unwrap(), &self.svg_area, &self.screen_area);
-                }
-                &Position::Relative => {
-                    pos1 = SvgPoint::new(*params.get(0).unwrap() + pos0.x(), *params.get(1).unwrap() + pos0.y(), &self.svg_area, &self.screen_area);
-                    pos2 = SvgPoint::new(*params.get(2).unwrap() + pos0.x(), *params.get(3).unwrap() + pos0.y(), &self.svg_area, &self.screen_area);
-                    pos3 = SvgPoint::new(*params.get(4).unwrap() + pos0.x(), *params.get(5).unwrap() + pos0.y(), &self.svg_area, &self.screen_area);
-                }
-            }
-            let mut index = 0i32;
-            while index <= 100 {
-                let t = (index as f32) / 100f32;
-                let x = (1f32-t).powi(3) * pos0.x() + 3f32*(1f32-t).powi(2) * t * pos1.x() + 3f32*(1f32-t) * t.powi(2) * pos2.x() + t.powi(3) * pos3.x();
-                let y = (1f32-t).powi(3) * pos0.y() + 3f32*(1f32-t).powi(2) * t * pos1.y() + 3f32*(1f32-t) * t.powi(2) * pos2.y() + t.powi(3) * pos3.y();
-                line_coords.push(x);
-                line_coords.push(y);
-                index += 10;
-            }
+        let mut current_point = self.current_point.clone();
+        let mut second_control_point = current_point.clone();
+        for curve_params in params.chunks(6) {
+            let first_control_point = self.point_from_params(position_type, &current_point, curve_params[0], curve_params[1]);
+            second_control_point = self.point_from_params(position_type, &current_point, curve_params[2], curve_params[3]);
+            let end_point = self.point_from_params(position_type, &current_point, curve_params[4], curve_params[5]);
+            let points = flatten(|t| cubic_curve_point(&current_point, &first_control_point, &second_control_point, &end_point, t),
+                                 |point| self.to_screen(point), self.tolerance);
+            push_coords(&mut line_coords, &points);
+            current_point = end_point;
+        }
+
+        self.perform_line(&Position::Absolute, &line_coords)?;
+        self.last_control_point = Some((CurveKind::Cubic, second_control_point));
+        Ok(())
+    }
+
+    fn perform_smooth_cubic_curve(&mut self, position_type: &Position, params: &[f32],
+                                  previous_control_point: Option<(CurveKind, SvgPoint)>) -> Result<(), Error> {
+        if params.is_empty() || !params.len().is_multiple_of(4) {
+            return Err(Error::path_data(format!("Smooth cubic curve expects groups of 4 params, got {}", params.len())));
+        }
+
+        let mut line_coords: Vec<f32> = Vec::new();
+        let mut current_point = self.current_point.clone();
+        let mut previous_control_point = previous_control_point;
+        for curve_params in params.chunks(4) {
+            let first_control_point = match previous_control_point {
+                Some((CurveKind::Cubic, ref previous)) => reflect(previous, &current_point),
+                _ => current_point.clone(),
+            };
+            let second_control_point = self.point_from_params(position_type, &current_point, curve_params[0], curve_params[1]);
+            let end_point = self.point_from_params(position_type, &current_point, curve_params[2], curve_params[3]);
+            let points = flatten(|t| cubic_curve_point(&current_point, &first_control_point, &second_control_point, &end_point, t),
+                                 |point| self.to_screen(point), self.tolerance);
+            push_coords(&mut line_coords, &points);
+            current_point = end_point;
+            previous_control_point = Some((CurveKind::Cubic, second_control_point));
+        }
+
+        self.perform_line(&Position::Absolute, &line_coords)?;
+        self.last_control_point = previous_control_point;
+        Ok(())
+    }
+
+    fn perform_quadratic_curve(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        if params.is_empty() || !params.len().is_multiple_of(4) {
+            return Err(Error::path_data(format!("Quadratic curve expects groups of 4 params, got {}", params.len())));
+        }
+
+        let mut line_coords: Vec<f32> = Vec::new();
+        let mut current_point = self.current_point.clone();
+        let mut control_point = current_point.clone();
+        for curve_params in params.chunks(4) {
+            control_point = self.point_from_params(position_type, &current_point, curve_params[0], curve_params[1]);
+            let end_point = self.point_from_params(position_type, &current_point, curve_params[2], curve_params[3]);
+            let points = flatten(|t| quadratic_curve_point(&current_point, &control_point, &end_point, t),
+                                 |point| self.to_screen(point), self.tolerance);
+            push_coords(&mut line_coords, &points);
+            current_point = end_point;
+        }
+
+        self.perform_line(&Position::Absolute, &line_coords)?;
+        self.last_control_point = Some((CurveKind::Quadratic, control_point));
+        Ok(())
+    }
+
+    fn perform_smooth_quadratic_curve(&mut self, position_type: &Position, params: &[f32],
+                                      previous_control_point: Option<(CurveKind, SvgPoint)>) -> Result<(), Error> {
+        if params.is_empty() || !params.len().is_multiple_of(2) {
+            return Err(Error::path_data(format!("Smooth quadratic curve expects pairs of params, got {}", params.len())));
+        }
+
+        let mut line_coords: Vec<f32> = Vec::new();
+        let mut current_point = self.current_point.clone();
+        let mut previous_control_point = previous_control_point;
+        for curve_params in params.chunks(2) {
+            let control_point = match previous_control_point {
+                Some((CurveKind::Quadratic, ref previous)) => reflect(previous, &current_point),
+                _ => current_point.clone(),
+            };
+            let end_point = self.point_from_params(position_type, &current_point, curve_params[0], curve_params[1]);
+            let points = flatten(|t| quadratic_curve_point(&current_point, &control_point, &end_point, t),
+                                 |point| self.to_screen(point), self.tolerance);
+            push_coords(&mut line_coords, &points);
+            current_point = end_point;
+            previous_control_point = Some((CurveKind::Quadratic, control_point));
+        }
+
+        self.perform_line(&Position::Absolute, &line_coords)?;
+        self.last_control_point = previous_control_point;
+        Ok(())
+    }
+
+    fn point_from_params(&self, position_type: &Position, current_point: &SvgPoint, x: f32, y: f32) -> SvgPoint {
+        match *position_type {
+            Position::Absolute => SvgPoint::new(x, y),
+            Position::Relative => current_point.clone().offset(x, y),
         }
-        
-        self.perform_line(&Position::Absolute, &line_coords)
     }
 
-    fn perform_horizontal_line(&mut self, position_type: &Position, params: &Vec<f32>) -> Result<(), MyError> {
+    fn to_screen(&self, point: &SvgPoint) -> ScreenPoint {
+        point.to_screen(&self.transform)
+    }
+
+    fn perform_horizontal_line(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        if params.is_empty() {
+            return Err(Error::path_data("Horizontal line expects at least 1 param, got 0".to_string()));
+        }
         let mut line_coords: Vec<f32> = Vec::new();
         let mut current_point = self.current_point.clone();
         for &param in params {
-            match position_type {
-                &Position::Relative => {
+            match *position_type {
+                Position::Relative => {
                     current_point = current_point.offset(param, 0f32);
                 }
-                &Position::Absolute => {
-                    current_point = SvgPoint::new(param, current_point.y(), &self.svg_area, &self.screen_area);
+                Position::Absolute => {
+                    current_point = SvgPoint::new(param, current_point.y());
                 }
             }
             line_coords.push(current_point.x());
@@ -194,16 +308,19 @@ impl Painter {
         self.perform_line(&Position::Absolute, &line_coords)
     }
 
-    fn perform_vertical_line(&mut self, position_type: &Position, params: &Vec<f32>) -> Result<(), MyError> {
+    fn perform_vertical_line(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        if params.is_empty() {
+            return Err(Error::path_data("Vertical line expects at least 1 param, got 0".to_string()));
+        }
         let mut line_coords: Vec<f32> = Vec::new();
         let mut current_point = self.current_point.clone();
         for &param in params {
-            match position_type {
-                &Position::Relative => {
+            match *position_type {
+                Position::Relative => {
                     current_point = current_point.offset(0f32, param);
                 }
-                &Position::Absolute => {
-                    current_point = SvgPoint::new(current_point.x(), param, &self.svg_area, &self.screen_area);
+                Position::Absolute => {
+                    current_point = SvgPoint::new(current_point.x(), param);
                 }
             }
             line_coords.push(current_point.x());
@@ -212,24 +329,87 @@ impl Painter {
         self.perform_line(&Position::Absolute, &line_coords)
     }
 
-    pub fn perform_close(&mut self) -> Result<(), MyError> {
-        if self.subpath_initial_point.is_none() {
-            return Err(MyError::new("For some reason subpath_initial_point is_none in perform_close".to_string()));
+    fn perform_elliptical_arc(&mut self, position_type: &Position, params: &[f32]) -> Result<(), Error> {
+        if params.is_empty() || !params.len().is_multiple_of(7) {
+            return Err(Error::path_data(format!("Elliptical arc expects groups of 7 params, got {}", params.len())));
         }
+
         let mut line_coords: Vec<f32> = Vec::new();
-        line_coords.push(self.current_point.x());
-        line_coords.push(self.current_point.y());
-        line_coords.push(self.subpath_initial_point.as_ref().unwrap().x());
-        line_coords.push(self.subpath_initial_point.as_ref().unwrap().y());
+        let mut current_point = self.current_point.clone();
+        for arc_params in params.chunks(7) {
+            let (radius_x, radius_y, x_axis_rotation) = (arc_params[0], arc_params[1], arc_params[2]);
+            let large_arc = arc_params[3] != 0f32;
+            let sweep = arc_params[4] != 0f32;
+            let end_point = match *position_type {
+                Position::Absolute => SvgPoint::new(arc_params[5], arc_params[6]),
+                Position::Relative => current_point.clone().offset(arc_params[5], arc_params[6]),
+            };
+
+            let arc = Arc::from_endpoints(current_point.x(), current_point.y(),
+                                          radius_x, radius_y, x_axis_rotation,
+                                          large_arc, sweep,
+                                          end_point.x(), end_point.y());
+            if let Some(arc) = arc {
+                let mut points = flatten(|t| {
+                    let (x, y) = arc.point_at(t);
+                    SvgPoint::new(x, y)
+                }, |point| self.to_screen(point), self.tolerance);
+                // Exact end point is pushed below.
+                points.pop();
+                push_coords(&mut line_coords, &points);
+            }
+            line_coords.push(end_point.x());
+            line_coords.push(end_point.y());
+            current_point = end_point;
+        }
+        self.perform_line(&Position::Absolute, &line_coords)
+    }
+
+    pub fn perform_close(&mut self) -> Result<(), Error> {
+        let initial_point = match self.subpath_initial_point {
+            Some(ref initial_point) => initial_point.clone(),
+            None => return Err(Error::path_data("Close without a subpath to close".to_string())),
+        };
+        let line_coords = vec![
+            self.current_point.x(),
+            self.current_point.y(),
+            initial_point.x(),
+            initial_point.y(),
+        ];
         self.perform_line(&Position::Absolute, &line_coords)?;
         self.subpath_initial_point = None;
         Ok(())
     }
 }
 
-fn print_params(command_name: &str, params: &Parameters) {
-    println!("{}! params:", command_name);
-    for param in params.iter() {
-        println!("\t{}", param);
+// Move and line commands take one or more pairs of coordinates.
+fn check_pairs(command: &str, params: &[f32]) -> Result<(), Error> {
+    if params.is_empty() || !params.len().is_multiple_of(2) {
+        return Err(Error::path_data(format!("{} expects pairs of params, got {}", command, params.len())));
     }
-}
\ No newline at end of file
+    Ok(())
+}
+
+/// Reflection of `point` relative to `center`.
+fn reflect(point: &SvgPoint, center: &SvgPoint) -> SvgPoint {
+    center.clone().offset(center.x() - point.x(), center.y() - point.y())
+}
+
+fn push_coords(line_coords: &mut Vec<f32>, points: &[SvgPoint]) {
+    for point in points {
+        line_coords.push(point.x());
+        line_coords.push(point.y());
+    }
+}
+
+fn cubic_curve_point(pos0: &SvgPoint, pos1: &SvgPoint, pos2: &SvgPoint, pos3: &SvgPoint, t: f32) -> SvgPoint {
+    let x = (1f32-t).powi(3) * pos0.x() + 3f32*(1f32-t).powi(2) * t * pos1.x() + 3f32*(1f32-t) * t.powi(2) * pos2.x() + t.powi(3) * pos3.x();
+    let y = (1f32-t).powi(3) * pos0.y() + 3f32*(1f32-t).powi(2) * t * pos1.y() + 3f32*(1f32-t) * t.powi(2) * pos2.y() + t.powi(3) * pos3.y();
+    SvgPoint::new(x, y)
+}
+
+fn quadratic_curve_point(pos0: &SvgPoint, pos1: &SvgPoint, pos2: &SvgPoint, t: f32) -> SvgPoint {
+    let x = (1f32-t).powi(2) * pos0.x() + 2f32*(1f32-t) * t * pos1.x() + t.powi(2) * pos2.x();
+    let y = (1f32-t).powi(2) * pos0.y() + 2f32*(1f32-t) * t * pos1.y() + t.powi(2) * pos2.y();
+    SvgPoint::new(x, y)
+}
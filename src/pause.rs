@@ -0,0 +1,47 @@
+use std::io::{self, BufRead};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// Flag which pauses drawing, shared between the executor and whoever controls it.
+#[derive(Clone, Debug, Default)]
+pub struct PauseSwitch {
+    paused: Arc<AtomicBool>,
+}
+
+impl PauseSwitch {
+    pub fn new() -> PauseSwitch {
+        PauseSwitch::default()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    /// Returns whether drawing is paused now.
+    pub fn toggle(&self) -> bool {
+        !self.paused.fetch_xor(true, Ordering::SeqCst)
+    }
+
+    /// Toggles the switch every time Enter is pressed in the terminal.
+    pub fn listen_to_stdin(&self) {
+        let switch = self.clone();
+        thread::spawn(move || {
+            let stdin = io::stdin();
+            for line in stdin.lock().lines() {
+                if line.is_err() {
+                    break;
+                }
+                if switch.toggle() {
+                    eprintln!("Paused after the current stroke, press Enter to resume");
+                } else {
+                    eprintln!("Resumed");
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use error::Error;
+use backend::PointerBackend;
+
+/// Backend wrapper which remembers whether the button is pressed
+/// and releases it when dropped, including drops caused by errors and panics.
+pub struct PenGuard<B: PointerBackend> {
+    backend: B,
+    pressed: bool,
+}
+
+impl<B: PointerBackend> PenGuard<B> {
+    pub fn new(backend: B) -> PenGuard<B> {
+        PenGuard {
+            backend,
+            pressed: false,
+        }
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+}
+
+impl<B: PointerBackend> Drop for PenGuard<B> {
+    fn drop(&mut self) {
+        if self.pressed {
+            let _ = self.backend.release();
+            let _ = self.backend.flush();
+        }
+    }
+}
+
+impl<B: PointerBackend> PointerBackend for PenGuard<B> {
+    fn press(&mut self) -> Result<(), Error> {
+        // Counted as pressed even if pressing fails: the button may still have gone down.
+        self.pressed = true;
+        self.backend.press()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.backend.release()?;
+        self.pressed = false;
+        Ok(())
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.backend.move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        self.backend.position()
+    }
+
+    fn pause(&mut self, duration: Duration) -> Result<(), Error> {
+        self.backend.pause(duration)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.backend.flush()
+    }
+}
@@ -1,26 +1,20 @@
-use std::convert::From;
-use rect::Rect;
+use transform::Transform;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SvgPoint {
     x: f32,
     y: f32,
-    svg_area: Rect,
-    screen_area: Rect,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ScreenPoint {
     x: f32,
     y: f32,
-    svg_area: Rect,
-    screen_area: Rect,
 }
 
-#[allow(dead_code)]
 impl SvgPoint {
-    pub fn new(x: f32, y: f32, svg_area: &Rect, screen_area: &Rect) -> SvgPoint {
-        SvgPoint{ x: x, y: y, svg_area: svg_area.clone(), screen_area: screen_area.clone() }
+    pub fn new(x: f32, y: f32) -> SvgPoint {
+        SvgPoint{ x, y }
     }
 
     pub fn x(&self) -> f32 {
@@ -31,41 +25,20 @@ impl SvgPoint {
         self.y
     }
 
-    pub fn svg_area(&self) -> &Rect {
-        &self.svg_area
-    }
-
-    pub fn screen_area(&self) -> &Rect {
-        &self.screen_area
-    }
-
     pub fn offset(self, x: f32, y: f32) -> SvgPoint {
-        SvgPoint::new(self.x + x, self.y + y, &self.svg_area, &self.screen_area)
+        SvgPoint::new(self.x + x, self.y + y)
     }
-}
 
-impl From<SvgPoint> for ScreenPoint {
-    fn from(svg_point: SvgPoint) -> Self {
-        let (svg_x, svg_y, screen_area, svg_area) = (svg_point.x, svg_point.y, svg_point.screen_area, svg_point.svg_area);
-        let new_x = svg_x * screen_area.width()/svg_area.width() + screen_area.x();
-        let new_y = svg_y * screen_area.height()/svg_area.height() + screen_area.y();
-        ScreenPoint::new(new_x, new_y, &svg_area, &screen_area)
+    /// Maps the point onto the screen.
+    pub fn to_screen(&self, svg_to_screen: &Transform) -> ScreenPoint {
+        let (x, y) = svg_to_screen.apply(self.x, self.y);
+        ScreenPoint::new(x, y)
     }
 }
 
-impl From<ScreenPoint> for SvgPoint {
-    fn from(screen_point: ScreenPoint) -> Self {
-        let (screen_x, screen_y, screen_area, svg_area) = (screen_point.x, screen_point.y, screen_point.screen_area, screen_point.svg_area);
-        let new_x = (screen_x - screen_area.x())*(svg_area.width()/screen_area.width());
-        let new_y = (screen_y - screen_area.y())*(svg_area.height()/screen_area.height());
-        SvgPoint::new(new_x, new_y, &svg_area, &screen_area)
-    }
-}
-
-#[allow(dead_code)]
 impl ScreenPoint {
-    pub fn new(x: f32, y: f32, svg_area: &Rect, screen_area: &Rect) -> ScreenPoint {
-        ScreenPoint{ x: x, y: y, svg_area: svg_area.clone(), screen_area: screen_area.clone() }
+    pub fn new(x: f32, y: f32) -> ScreenPoint {
+        ScreenPoint{ x, y }
     }
 
     pub fn x(&self) -> f32 {
@@ -76,15 +49,33 @@ impl ScreenPoint {
         self.y
     }
 
-    pub fn svg_area(&self) -> &Rect {
-        &self.svg_area
+    pub fn offset(self, x: f32, y: f32) -> ScreenPoint {
+        ScreenPoint::new(self.x + x, self.y + y)
     }
 
-    pub fn screen_area(&self) -> &Rect {
-        &self.screen_area
+    pub fn distance_to(&self, other: &ScreenPoint) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
     }
 
-    pub fn offset(self, x: f32, y: f32) -> ScreenPoint {
-        ScreenPoint::new(self.x + x, self.y + y, &self.svg_area, &self.screen_area)
+    /// Distance to the closest point of the segment between `start` and `end`.
+    pub fn distance_to_segment(&self, start: &ScreenPoint, end: &ScreenPoint) -> f32 {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let (px, py) = (self.x - start.x, self.y - start.y);
+        let length_squared = dx * dx + dy * dy;
+        let t = if length_squared > 0f32 {
+            ((px * dx + py * dy) / length_squared).clamp(0f32, 1f32)
+        } else {
+            0f32
+        };
+        ((px - t * dx).powi(2) + (py - t * dy).powi(2)).sqrt()
     }
-}
\ No newline at end of file
+
+    /// Maps the point back into the SVG, `svg_to_screen` is the transform the point was mapped onto the screen with.
+    /// Returns None if the transform isn't invertible.
+    pub fn to_svg(&self, svg_to_screen: &Transform) -> Option<SvgPoint> {
+        svg_to_screen.inverse().map(|screen_to_svg| {
+            let (x, y) = screen_to_svg.apply(self.x, self.y);
+            SvgPoint::new(x, y)
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use error::Error;
+use rect::Rect;
+
+/// Named paint areas saved by the `calibrate` command, one per line: `name x y width height`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Profiles {
+    areas: BTreeMap<String, Rect>,
+}
+
+impl Profiles {
+    pub fn new() -> Profiles {
+        Profiles::default()
+    }
+
+    /// `$XDG_CONFIG_HOME/svg-bot/profiles`, or `~/.config/svg-bot/profiles` without XDG_CONFIG_HOME.
+    pub fn default_path() -> Option<PathBuf> {
+        let config = match env::var_os("XDG_CONFIG_HOME") {
+            Some(ref config) if !config.is_empty() => PathBuf::from(config),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config.join("svg-bot").join("profiles"))
+    }
+
+    /// Reads profiles from `path`, a missing file means there are no profiles yet.
+    pub fn load(path: &Path) -> Result<Profiles, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(ref error) if error.kind() == ErrorKind::NotFound => return Ok(Profiles::new()),
+            Err(error) => return Err(Error::io(format!("Couldn't read profiles {}", path.display()), error)),
+        };
+        let mut profiles = Profiles::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || Error::Parse(format!("Couldn't parse line {} of {}: '{}'", number + 1, path.display(), line));
+            let words: Vec<&str> = line.split_whitespace().collect();
+            if words.len() != 5 {
+                return Err(error());
+            }
+            let numbers = words[1..].iter()
+                .map(|number| number.parse::<f32>().map_err(|_| error()))
+                .collect::<Result<Vec<f32>, Error>>()?;
+            profiles.areas.insert(words[0].to_string(), Rect::new(numbers[0], numbers[1], numbers[2], numbers[3]));
+        }
+        Ok(profiles)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut content = String::new();
+        for (name, area) in &self.areas {
+            content.push_str(&format!("{} {} {} {} {}\n", name, area.x(), area.y(), area.width(), area.height()));
+        }
+        path.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, content))
+            .map_err(|error| Error::io(format!("Couldn't write profiles {}", path.display()), error))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Rect> {
+        self.areas.get(name)
+    }
+
+    /// Adds or replaces a profile, its name must pass `validate_name`.
+    pub fn set(&mut self, name: &str, area: Rect) -> Result<(), Error> {
+        validate_name(name)?;
+        self.areas.insert(name.to_string(), area);
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.areas.keys().map(String::as_str).collect()
+    }
+}
+
+/// Profile names can't be empty, contain whitespace or start with '#', which starts a comment in the file.
+pub fn validate_name(name: &str) -> Result<(), Error> {
+    if name.is_empty() || name.contains(char::is_whitespace) || name.starts_with('#') {
+        return Err(Error::Calibration(format!("Invalid profile name '{}'", name)));
+    }
+    Ok(())
+}
+
+/// Parses a paint area given as "x,y,width,height".
+pub fn parse_area(area: &str) -> Result<Rect, Error> {
+    let error = || Error::Parse(format!("Area must be x,y,width,height with positive width and height, got '{}'", area));
+    let numbers = area.split(',')
+        .map(|number| number.trim().parse::<f32>().map_err(|_| error()))
+        .collect::<Result<Vec<f32>, Error>>()?;
+    if numbers.len() != 4 || numbers[2] <= 0f32 || numbers[3] <= 0f32 {
+        return Err(error());
+    }
+    Ok(Rect::new(numbers[0], numbers[1], numbers[2], numbers[3]))
+}
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use error::Error;
+use backend::PointerBackend;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PointerEventKind {
+    Press,
+    Release,
+    Move(f32, f32),
+}
+
+#[derive(Clone, Debug)]
+pub struct PointerEvent {
+    time: Duration,
+    kind: PointerEventKind,
+}
+
+impl PointerEvent {
+    /// Time elapsed since the backend was created.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn kind(&self) -> &PointerEventKind {
+        &self.kind
+    }
+}
+
+/// Backend which doesn't touch any real pointer and only remembers what it was asked to do.
+/// Pauses don't sleep, they only shift the time of the following events.
+pub struct RecordingBackend {
+    started: Instant,
+    paused: Duration,
+    events: Vec<PointerEvent>,
+    position: (f32, f32),
+}
+
+impl RecordingBackend {
+    pub fn new() -> RecordingBackend {
+        RecordingBackend {
+            started: Instant::now(),
+            paused: Duration::from_millis(0),
+            events: Vec::new(),
+            position: (0f32, 0f32),
+        }
+    }
+
+    pub fn events(&self) -> &[PointerEvent] {
+        &self.events
+    }
+
+    /// Polylines drawn while the button was pressed.
+    /// Consecutive duplicate points are skipped because they don't change the drawing.
+    pub fn strokes(&self) -> Vec<Vec<(f32, f32)>> {
+        let mut strokes: Vec<Vec<(f32, f32)>> = Vec::new();
+        let mut position = (0f32, 0f32);
+        let mut pressed = false;
+        for event in &self.events {
+            match event.kind {
+                PointerEventKind::Press => {
+                    if !pressed {
+                        strokes.push(vec![position]);
+                        pressed = true;
+                    }
+                }
+                PointerEventKind::Release => {
+                    pressed = false;
+                }
+                PointerEventKind::Move(x, y) => {
+                    position = (x, y);
+                    if pressed {
+                        let stroke = strokes.last_mut().unwrap();
+                        if *stroke.last().unwrap() != position {
+                            stroke.push(position);
+                        }
+                    }
+                }
+            }
+        }
+        strokes
+    }
+
+    fn record(&mut self, kind: PointerEventKind) {
+        let time = self.started.elapsed() + self.paused;
+        self.events.push(PointerEvent{ time, kind });
+    }
+}
+
+impl Default for RecordingBackend {
+    fn default() -> Self {
+        RecordingBackend::new()
+    }
+}
+
+impl PointerBackend for RecordingBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.record(PointerEventKind::Press);
+        Ok(())
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.record(PointerEventKind::Release);
+        Ok(())
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.position = (x, y);
+        self.record(PointerEventKind::Move(x, y));
+        Ok(())
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        Ok(self.position)
+    }
+
+    fn pause(&mut self, duration: Duration) -> Result<(), Error> {
+        self.paused += duration;
+        Ok(())
+    }
+}
@@ -8,7 +8,7 @@ pub struct Rect {
 
 impl Rect {
     pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rect {
-        Rect{ x: x, y: y, width: width, height: height }
+        Rect{ x, y, width, height }
     }
 
     pub fn x(&self) -> f32 {
@@ -0,0 +1,196 @@
+use svg::node::Attributes;
+use svg::node::element::path::Data;
+use svg::node::element::tag;
+
+use error::Error;
+use length::Length;
+use rect::Rect;
+use transform;
+
+// Attributes holding the lengths shapes are made of.
+const LENGTH_ATTRIBUTES: [&str; 13] = ["x", "y", "width", "height", "rx", "ry", "cx", "cy", "r", "x1", "y1", "x2", "y2"];
+
+/// Turns a drawable element into path data which `Painter` can perform.
+/// Returns None for elements which don't describe any geometry
+/// and for shapes which mustn't be rendered, e.g. a rect with zero width.
+/// Percentages are resolved against `viewport`, shapes using them aren't drawn when it's unknown.
+pub fn element_data(name: &str, attributes: &Attributes, viewport: Option<&Rect>) -> Result<Option<Data>, Error> {
+    if viewport.is_none() && LENGTH_ATTRIBUTES.iter()
+            .any(|&name| attributes.get(name).is_some_and(|value| value.trim().ends_with('%'))) {
+        return Ok(None);
+    }
+    match name {
+        tag::Path => path_data(attributes),
+        tag::Rectangle => rect_data(attributes, viewport),
+        tag::Circle => circle_data(attributes, viewport),
+        tag::Ellipse => ellipse_data(attributes, viewport),
+        tag::Line => line_data(attributes, viewport),
+        tag::Polyline => poly_data(attributes, false),
+        tag::Polygon => poly_data(attributes, true),
+        _ => Ok(None),
+    }
+}
+
+fn path_data(attributes: &Attributes) -> Result<Option<Data>, Error> {
+    match attributes.get("d") {
+        Some(data) => Data::parse(data).map(Some).map_err(|error| Error::PathData {
+            element_id: attributes.get("id").map(|id| id.to_string()),
+            offset: invalid_command_offset(data),
+            message: "Couldn't parse path data".to_string(),
+            source: Some(error),
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Byte offset of the `index`th command in path data.
+pub fn command_offset(data: &str, index: usize) -> Option<usize> {
+    command_offsets(data).get(index).cloned()
+}
+
+// Every letter but an exponent starts a command, unknown ones included.
+fn command_offsets(data: &str) -> Vec<usize> {
+    data.char_indices()
+        .filter(|&(_, c)| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+// Commands are parsed independently of each other, so the invalid one is the first which fails on its own.
+fn invalid_command_offset(data: &str) -> Option<usize> {
+    let mut offsets = command_offsets(data);
+    if offsets.first() != Some(&0) {
+        offsets.insert(0, 0);
+    }
+    let ends = offsets.iter().skip(1).cloned().chain(Some(data.len()));
+    offsets.iter().zip(ends)
+        .find(|&(&start, end)| Data::parse(&data[start..end]).is_err())
+        .map(|(&start, _)| start)
+}
+
+fn rect_data(attributes: &Attributes, viewport: Option<&Rect>) -> Result<Option<Data>, Error> {
+    let x = number_attribute(attributes, "x", viewport)?.unwrap_or(0f32);
+    let y = number_attribute(attributes, "y", viewport)?.unwrap_or(0f32);
+    let width = number_attribute(attributes, "width", viewport)?.unwrap_or(0f32);
+    let height = number_attribute(attributes, "height", viewport)?.unwrap_or(0f32);
+    if width <= 0f32 || height <= 0f32 {
+        return Ok(None);
+    }
+
+    // A missing radius takes the value of the other one, see
+    // https://www.w3.org/TR/SVG/shapes.html#RectElement
+    let rx = number_attribute(attributes, "rx", viewport)?;
+    let ry = number_attribute(attributes, "ry", viewport)?;
+    let (rx, ry) = match (rx, ry) {
+        (Some(rx), Some(ry)) => (rx, ry),
+        (Some(rx), None) => (rx, rx),
+        (None, Some(ry)) => (ry, ry),
+        (None, None) => (0f32, 0f32),
+    };
+    let rx = rx.max(0f32).min(width / 2f32);
+    let ry = ry.max(0f32).min(height / 2f32);
+
+    if rx == 0f32 || ry == 0f32 {
+        return Ok(Some(Data::new()
+            .move_to((x, y))
+            .horizontal_line_to(x + width)
+            .vertical_line_to(y + height)
+            .horizontal_line_to(x)
+            .close()));
+    }
+
+    Ok(Some(Data::new()
+        .move_to((x + rx, y))
+        .horizontal_line_to(x + width - rx)
+        .elliptical_arc_to((rx, ry, 0, 0, 1, x + width, y + ry))
+        .vertical_line_to(y + height - ry)
+        .elliptical_arc_to((rx, ry, 0, 0, 1, x + width - rx, y + height))
+        .horizontal_line_to(x + rx)
+        .elliptical_arc_to((rx, ry, 0, 0, 1, x, y + height - ry))
+        .vertical_line_to(y + ry)
+        .elliptical_arc_to((rx, ry, 0, 0, 1, x + rx, y))
+        .close()))
+}
+
+fn circle_data(attributes: &Attributes, viewport: Option<&Rect>) -> Result<Option<Data>, Error> {
+    let cx = number_attribute(attributes, "cx", viewport)?.unwrap_or(0f32);
+    let cy = number_attribute(attributes, "cy", viewport)?.unwrap_or(0f32);
+    let r = number_attribute(attributes, "r", viewport)?.unwrap_or(0f32);
+    Ok(ellipse(cx, cy, r, r))
+}
+
+fn ellipse_data(attributes: &Attributes, viewport: Option<&Rect>) -> Result<Option<Data>, Error> {
+    let cx = number_attribute(attributes, "cx", viewport)?.unwrap_or(0f32);
+    let cy = number_attribute(attributes, "cy", viewport)?.unwrap_or(0f32);
+    let rx = number_attribute(attributes, "rx", viewport)?.unwrap_or(0f32);
+    let ry = number_attribute(attributes, "ry", viewport)?.unwrap_or(0f32);
+    Ok(ellipse(cx, cy, rx, ry))
+}
+
+fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> Option<Data> {
+    if rx <= 0f32 || ry <= 0f32 {
+        return None;
+    }
+    Some(Data::new()
+        .move_to((cx + rx, cy))
+        .elliptical_arc_to((rx, ry, 0, 0, 1, cx - rx, cy))
+        .elliptical_arc_to((rx, ry, 0, 0, 1, cx + rx, cy))
+        .close())
+}
+
+fn line_data(attributes: &Attributes, viewport: Option<&Rect>) -> Result<Option<Data>, Error> {
+    let x1 = number_attribute(attributes, "x1", viewport)?.unwrap_or(0f32);
+    let y1 = number_attribute(attributes, "y1", viewport)?.unwrap_or(0f32);
+    let x2 = number_attribute(attributes, "x2", viewport)?.unwrap_or(0f32);
+    let y2 = number_attribute(attributes, "y2", viewport)?.unwrap_or(0f32);
+    Ok(Some(Data::new().move_to((x1, y1)).line_to((x2, y2))))
+}
+
+fn poly_data(attributes: &Attributes, closed: bool) -> Result<Option<Data>, Error> {
+    let points = match attributes.get("points") {
+        Some(points) => parse_points(points)?,
+        None => return Ok(None),
+    };
+    if points.len() < 2 {
+        return Ok(None);
+    }
+
+    let mut data = Data::new().move_to((points[0], points[1]));
+    if points.len() > 2 {
+        data = data.line_to(points[2..].to_vec());
+    }
+    if closed {
+        data = data.close();
+    }
+    Ok(Some(data))
+}
+
+/// Parses `points` attribute of polyline and polygon: coordinates separated by whitespace, commas or signs.
+/// An odd coordinate at the end is dropped, as the spec says to render everything up to the error.
+pub fn parse_points(points: &str) -> Result<Vec<f32>, Error> {
+    let mut coords = transform::parse_numbers(points)
+        .map_err(|coord| Error::Parse(format!("Couldn't parse coordinate '{}' of points '{}'", coord, points)))?;
+    if coords.len() % 2 != 0 {
+        coords.pop();
+    }
+    Ok(coords)
+}
+
+// Percentages of x coordinates and widths are relative to the viewport's width, those of y coordinates and heights
+// to its height and those of other lengths to its normalized diagonal,
+// see https://www.w3.org/TR/SVG/coords.html#Units
+fn number_attribute(attributes: &Attributes, name: &str, viewport: Option<&Rect>) -> Result<Option<f32>, Error> {
+    match attributes.get(name) {
+        Some(value) => {
+            let length = Length::parse(value)
+                .map_err(|_| Error::Parse(format!("Couldn't parse attribute {}='{}'", name, value.trim())))?;
+            let percent_base = viewport.map(|viewport| match name {
+                "x" | "width" | "rx" | "cx" | "x1" | "x2" => viewport.width(),
+                "y" | "height" | "ry" | "cy" | "y1" | "y2" => viewport.height(),
+                _ => ((viewport.width().powi(2) + viewport.height().powi(2)) / 2f32).sqrt(),
+            });
+            Ok(length.to_px(percent_base))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,64 @@
+use point::ScreenPoint;
+use stroke_plan::{Stroke, StrokePlan};
+
+/// Default maximal distance in screen pixels between a stroke and its simplified version.
+pub const DEFAULT_EPSILON: f32 = 0.5f32;
+
+/// Simplifies every stroke of the plan, see `simplify_points`.
+pub fn simplify(plan: &StrokePlan, epsilon: f32) -> StrokePlan {
+    StrokePlan::new(plan.strokes().iter().map(|stroke| Stroke::new(
+        simplify_points(stroke.points(), epsilon),
+        stroke.element_id().map(String::from),
+        stroke.color().map(String::from))).collect())
+}
+
+/// Drops consecutive points which round to the same pixel,
+/// then removes points with Ramer–Douglas–Peucker so that the result
+/// deviates from the original polyline by no more than `epsilon` pixels.
+/// The first and the last points are always kept.
+pub fn simplify_points(points: &[ScreenPoint], epsilon: f32) -> Vec<ScreenPoint> {
+    let points = distinct_pixels(points);
+    if points.len() < 3 {
+        return points;
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // Explicit stack instead of recursion: traced images have strokes with lots of points.
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = ranges.pop() {
+        let mut farthest = (first, 0f32);
+        for (i, point) in points.iter().enumerate().take(last).skip(first + 1) {
+            let distance = point.distance_to_segment(&points[first], &points[last]);
+            if distance > farthest.1 {
+                farthest = (i, distance);
+            }
+        }
+        if farthest.1 > epsilon {
+            keep[farthest.0] = true;
+            ranges.push((first, farthest.0));
+            ranges.push((farthest.0, last));
+        }
+    }
+    points.into_iter().zip(keep).filter(|&(_, keep)| keep).map(|(point, _)| point).collect()
+}
+
+// The stroke keeps its exact last point even if an earlier point of the same pixel was kept instead.
+fn distinct_pixels(points: &[ScreenPoint]) -> Vec<ScreenPoint> {
+    let mut result: Vec<ScreenPoint> = Vec::with_capacity(points.len());
+    for point in points {
+        match result.last() {
+            Some(last) if same_pixel(last, point) => {}
+            _ => result.push(point.clone()),
+        }
+    }
+    if result.len() > 1 {
+        let last = result.len() - 1;
+        result[last] = points[points.len() - 1].clone();
+    }
+    result
+}
+
+fn same_pixel(a: &ScreenPoint, b: &ScreenPoint) -> bool {
+    a.x().round() == b.x().round() && a.y().round() == b.y().round()
+}
@@ -0,0 +1,49 @@
+use svg::node::Attributes;
+
+use error::Error;
+use rect::Rect;
+use length::Length;
+
+/// Parses a viewBox: 4 numbers separated by whitespace and/or commas, width and height must be positive.
+pub fn parse_view_box(value: &str) -> Result<Rect, Error> {
+    let numbers = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|number| !number.is_empty())
+        .map(|number| number.parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()
+        .map_err(|_| Error::Parse(format!("Couldn't parse viewBox '{}'", value)))?;
+    if numbers.len() != 4 {
+        return Err(Error::Parse(format!("viewBox must consist of 4 numbers, got '{}'", value)));
+    }
+    if numbers[2] <= 0f32 || numbers[3] <= 0f32 {
+        return Err(Error::Parse(format!("viewBox must have positive width and height, got '{}'", value)));
+    }
+    Ok(Rect::new(numbers[0], numbers[1], numbers[2], numbers[3]))
+}
+
+/// Area of the SVG in user units as declared by the root `svg` element:
+/// its viewBox, or else its width and height.
+/// Returns None if the root doesn't declare its size, e.g. when width and height are percentages,
+/// then the size has to be computed from the content.
+pub fn root_area(attributes: &Attributes) -> Result<Option<Rect>, Error> {
+    if let Some(view_box) = attributes.get("viewBox") {
+        return parse_view_box(view_box).map(Some);
+    }
+
+    let width = resolve_length(attributes, "width")?;
+    let height = resolve_length(attributes, "height")?;
+    match (width, height) {
+        (Some(width), Some(height)) if width > 0f32 && height > 0f32 => {
+            Ok(Some(Rect::new(0f32, 0f32, width, height)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn resolve_length(attributes: &Attributes, name: &str) -> Result<Option<f32>, Error> {
+    match attributes.get(name) {
+        // There's no parent viewport percentages could be resolved against.
+        Some(value) => Ok(Length::parse(value)?.to_px(None)),
+        None => Ok(None),
+    }
+}
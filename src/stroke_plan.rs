@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use point::ScreenPoint;
+use rect::Rect;
+
+/// Polyline drawn without lifting the pen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stroke {
+    points: Vec<ScreenPoint>,
+    element_id: Option<String>,
+    color: Option<String>,
+}
+
+impl Stroke {
+    pub fn new(points: Vec<ScreenPoint>, element_id: Option<String>, color: Option<String>) -> Stroke {
+        Stroke{ points, element_id, color }
+    }
+
+    pub fn points(&self) -> &[ScreenPoint] {
+        &self.points
+    }
+
+    /// Id of the SVG element the stroke was made from.
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_deref()
+    }
+
+    /// Stroke color of the SVG element the stroke was made from, as written in the SVG.
+    pub fn color(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+
+    /// The same stroke drawn from its end to its start.
+    pub fn reversed(&self) -> Stroke {
+        let mut stroke = self.clone();
+        stroke.points.reverse();
+        stroke
+    }
+}
+
+/// Everything a drawing consists of: strokes in screen coordinates in the order they are drawn.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StrokePlan {
+    strokes: Vec<Stroke>,
+}
+
+impl StrokePlan {
+    pub fn new(strokes: Vec<Stroke>) -> StrokePlan {
+        StrokePlan{ strokes }
+    }
+
+    pub fn strokes(&self) -> &[Stroke] {
+        &self.strokes
+    }
+
+    pub fn into_strokes(self) -> Vec<Stroke> {
+        self.strokes
+    }
+
+    pub fn push(&mut self, stroke: Stroke) {
+        self.strokes.push(stroke);
+    }
+
+    pub fn len(&self) -> usize {
+        self.strokes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strokes.is_empty()
+    }
+
+    /// Hash of everything the pen does, to tell whether a journal belongs to this plan.
+    /// Unlike `DefaultHasher` it's stable between builds. Uses 64 bit FNV-1a.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        let mut feed = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for stroke in &self.strokes {
+            feed(&(stroke.points.len() as u64).to_le_bytes());
+            for point in &stroke.points {
+                feed(&point.x().to_bits().to_le_bytes());
+                feed(&point.y().to_bits().to_le_bytes());
+            }
+        }
+        hash
+    }
+
+    /// Plan rendered as an SVG document with `area` of the screen as its viewBox:
+    /// pen-down strokes are black polylines, pen-up travels are dashed red lines.
+    pub fn to_svg(&self, area: &Rect) -> String {
+        let mut svg = String::new();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+                 area.width(), area.height(), area.x(), area.y(), area.width(), area.height()).unwrap();
+        svg.push_str("<g fill=\"none\" stroke-linecap=\"round\" stroke-linejoin=\"round\">\n");
+        let mut previous_end: Option<&ScreenPoint> = None;
+        for stroke in self.strokes.iter().filter(|stroke| !stroke.points.is_empty()) {
+            let start = &stroke.points[0];
+            if let Some(previous_end) = previous_end {
+                writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="red" stroke-width="0.5" stroke-dasharray="2 2"/>"#,
+                         previous_end.x(), previous_end.y(), start.x(), start.y()).unwrap();
+            }
+            svg.push_str("<polyline stroke=\"black\" points=\"");
+            push_svg_points(&mut svg, &stroke.points);
+            // A single point is drawn as a dot, so a degenerate polyline needs the point twice to be visible.
+            if stroke.points.len() == 1 {
+                svg.push(' ');
+                push_svg_points(&mut svg, &stroke.points);
+            }
+            svg.push_str("\"/>\n");
+            previous_end = stroke.points.last();
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    /// Plan as JSON: a list of pen-down strokes and pen-up travels between them in the order the pen makes them.
+    /// ```text
+    /// {"strokes":[{"pen":"down","element_id":"a","color":null,"points":[[0,0],[10,0]]},
+    ///             {"pen":"up","points":[[10,0],[20,5]]}, ...]}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"strokes\":[");
+        let mut previous_end: Option<&ScreenPoint> = None;
+        let mut first = true;
+        for stroke in self.strokes.iter().filter(|stroke| !stroke.points.is_empty()) {
+            if !first {
+                json.push(',');
+            }
+            first = false;
+            if let Some(previous_end) = previous_end {
+                json.push_str("{\"pen\":\"up\",\"points\":");
+                push_json_points(&mut json, &[previous_end.clone(), stroke.points[0].clone()]);
+                json.push_str("},");
+            }
+            json.push_str("{\"pen\":\"down\",\"element_id\":");
+            push_json_string(&mut json, stroke.element_id());
+            json.push_str(",\"color\":");
+            push_json_string(&mut json, stroke.color());
+            json.push_str(",\"points\":");
+            push_json_points(&mut json, &stroke.points);
+            json.push('}');
+            previous_end = stroke.points.last();
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+fn push_svg_points(svg: &mut String, points: &[ScreenPoint]) {
+    for (i, point) in points.iter().enumerate() {
+        if i > 0 {
+            svg.push(' ');
+        }
+        write!(svg, "{},{}", point.x(), point.y()).unwrap();
+    }
+}
+
+fn push_json_points(json: &mut String, points: &[ScreenPoint]) {
+    json.push('[');
+    for (i, point) in points.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(json, "[{},{}]", point.x(), point.y()).unwrap();
+    }
+    json.push(']');
+}
+
+fn push_json_string(json: &mut String, value: Option<&str>) {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            json.push_str("null");
+            return;
+        }
+    };
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
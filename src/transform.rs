@@ -0,0 +1,165 @@
+use error::Error;
+use rect::Rect;
+
+/// Affine transform in the SVG matrix notation:
+/// ```text
+/// | a c e |
+/// | b d f |
+/// | 0 0 1 |
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+}
+
+impl Transform {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Transform {
+        Transform{ a, b, c, d, e, f }
+    }
+
+    pub fn identity() -> Transform {
+        Transform::new(1f32, 0f32, 0f32, 1f32, 0f32, 0f32)
+    }
+
+    pub fn translate(tx: f32, ty: f32) -> Transform {
+        Transform::new(1f32, 0f32, 0f32, 1f32, tx, ty)
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Transform {
+        Transform::new(sx, 0f32, 0f32, sy, 0f32, 0f32)
+    }
+
+    /// Rotation by `angle` degrees around the origin.
+    pub fn rotate(angle: f32) -> Transform {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Transform::new(cos, sin, -sin, cos, 0f32, 0f32)
+    }
+
+    /// Rotation by `angle` degrees around (cx, cy).
+    pub fn rotate_around(angle: f32, cx: f32, cy: f32) -> Transform {
+        Transform::translate(cx, cy)
+            .multiply(&Transform::rotate(angle))
+            .multiply(&Transform::translate(-cx, -cy))
+    }
+
+    /// Skew along the x axis by `angle` degrees.
+    pub fn skew_x(angle: f32) -> Transform {
+        Transform::new(1f32, 0f32, angle.to_radians().tan(), 1f32, 0f32, 0f32)
+    }
+
+    /// Skew along the y axis by `angle` degrees.
+    pub fn skew_y(angle: f32) -> Transform {
+        Transform::new(1f32, angle.to_radians().tan(), 0f32, 1f32, 0f32, 0f32)
+    }
+
+    /// Transform which maps `from` onto `to`, scaling each axis independently.
+    /// Origins of both rects are respected, so e.g. a viewBox "10 10 100 100" starts exactly at the top left corner of `to`.
+    pub fn rect_to_rect(from: &Rect, to: &Rect) -> Transform {
+        Transform::translate(to.x(), to.y())
+            .multiply(&Transform::scale(to.width() / from.width(), to.height() / from.height()))
+            .multiply(&Transform::translate(-from.x(), -from.y()))
+    }
+
+    /// Parses value of the `transform` attribute, e.g. "translate(10, 20) rotate(45 5 5)".
+    pub fn parse(transform_list: &str) -> Result<Transform, Error> {
+        let mut result = Transform::identity();
+        let mut rest = transform_list;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            if rest.is_empty() {
+                return Ok(result);
+            }
+            let open = rest.find('(');
+            let close = rest.find(')');
+            let (open, close) = match (open, close) {
+                (Some(open), Some(close)) if open < close => (open, close),
+                _ => return Err(Error::Parse(format!("Couldn't parse transform '{}'", transform_list))),
+            };
+            let name = rest[..open].trim();
+            let args = parse_numbers(&rest[open + 1..close])
+                .map_err(|_| Error::Parse(format!("Couldn't parse arguments of {} in transform '{}'", name, transform_list)))?;
+            let transform = match (name, args.len()) {
+                ("matrix", 6) => Transform::new(args[0], args[1], args[2], args[3], args[4], args[5]),
+                ("translate", 1) => Transform::translate(args[0], 0f32),
+                ("translate", 2) => Transform::translate(args[0], args[1]),
+                ("scale", 1) => Transform::scale(args[0], args[0]),
+                ("scale", 2) => Transform::scale(args[0], args[1]),
+                ("rotate", 1) => Transform::rotate(args[0]),
+                ("rotate", 3) => Transform::rotate_around(args[0], args[1], args[2]),
+                ("skewX", 1) => Transform::skew_x(args[0]),
+                ("skewY", 1) => Transform::skew_y(args[0]),
+                _ => return Err(Error::Unsupported(format!(
+                    "Unsupported transform {} with {} arguments in '{}'", name, args.len(), transform_list))),
+            };
+            result = result.multiply(&transform);
+            rest = &rest[close + 1..];
+        }
+    }
+
+    /// Transform which applies `other` first and then `self`.
+    pub fn multiply(&self, other: &Transform) -> Transform {
+        Transform::new(
+            self.a * other.a + self.c * other.b,
+            self.b * other.a + self.d * other.b,
+            self.a * other.c + self.c * other.d,
+            self.b * other.c + self.d * other.d,
+            self.a * other.e + self.c * other.f + self.e,
+            self.b * other.e + self.d * other.f + self.f)
+    }
+
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.a * x + self.c * y + self.e,
+         self.b * x + self.d * y + self.f)
+    }
+
+    /// Returns None if the transform collapses the plane and thus can't be inverted.
+    pub fn inverse(&self) -> Option<Transform> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant == 0f32 || !determinant.is_finite() {
+            return None;
+        }
+        Some(Transform::new(
+            self.d / determinant,
+            -self.b / determinant,
+            -self.c / determinant,
+            self.a / determinant,
+            (self.c * self.f - self.d * self.e) / determinant,
+            (self.b * self.e - self.a * self.f) / determinant))
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+/// Parses a list of numbers separated by whitespace and/or commas.
+/// A sign starts a new number too, so compact lists like "10-5" are two numbers.
+/// The error is the first number which couldn't be parsed.
+pub fn parse_numbers(numbers: &str) -> Result<Vec<f32>, String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut previous: Option<char> = None;
+    for c in numbers.chars() {
+        let starts_new_number = c.is_whitespace() || c == ','
+            || ((c == '-' || c == '+') && !current.is_empty() && previous != Some('e') && previous != Some('E'));
+        if starts_new_number && !current.is_empty() {
+            result.push(current.parse::<f32>().map_err(|_| current.clone())?);
+            current.clear();
+        }
+        if !c.is_whitespace() && c != ',' {
+            current.push(c);
+        }
+        previous = Some(c);
+    }
+    if !current.is_empty() {
+        result.push(current.parse::<f32>().map_err(|_| current.clone())?);
+    }
+    Ok(result)
+}
@@ -0,0 +1,68 @@
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{self, ConnectionExt as XprotoConnectionExt, Window};
+use x11rb::protocol::xtest::{self, ConnectionExt as XtestConnectionExt};
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as WrapperConnectionExt;
+
+use error::Error;
+use backend::PointerBackend;
+
+const LEFT_BUTTON: u8 = 1;
+
+/// Backend which fakes pointer events right in the X server through the XTest extension,
+/// so neither the `xdotool` binary nor a process per event is needed.
+pub struct XTestBackend {
+    connection: RustConnection,
+    root: Window,
+}
+
+impl XTestBackend {
+    /// Connects to the display from the DISPLAY environment variable.
+    pub fn new() -> Result<XTestBackend, Error> {
+        XTestBackend::connect(None)
+    }
+
+    /// Connects to `display`, e.g. ":99".
+    pub fn connect(display: Option<&str>) -> Result<XTestBackend, Error> {
+        let (connection, screen) = x11rb::connect(display)?;
+        if connection.extension_information(xtest::X11_EXTENSION_NAME)?.is_none() {
+            return Err(Error::backend("X server doesn't support the XTEST extension".to_string()));
+        }
+        connection.xtest_get_version(2, 2)?.reply()?;
+        let root = connection.setup().roots[screen].root;
+        Ok(XTestBackend{ connection, root })
+    }
+
+    fn fake_input(&mut self, event_type: u8, detail: u8, x: i16, y: i16) -> Result<(), Error> {
+        // Time 0 means "now", device 0 is the core pointer.
+        self.connection.xtest_fake_input(event_type, detail, 0, self.root, x, y, 0)?;
+        self.connection.flush()?;
+        Ok(())
+    }
+}
+
+impl PointerBackend for XTestBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.fake_input(xproto::BUTTON_PRESS_EVENT, LEFT_BUTTON, 0, 0)
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.fake_input(xproto::BUTTON_RELEASE_EVENT, LEFT_BUTTON, 0, 0)
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        // Detail 0 makes the motion absolute, in coordinates of the root window.
+        self.fake_input(xproto::MOTION_NOTIFY_EVENT, 0, x.round() as i16, y.round() as i16)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        let pointer = self.connection.query_pointer(self.root)?.reply()?;
+        Ok((pointer.root_x as f32, pointer.root_y as f32))
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        // A round trip makes sure the server has processed all the faked events.
+        self.connection.sync()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use std::f32::consts::PI;
+use svg_bot::arc::Arc;
+
+#[test]
+fn semicircle_center_is_between_endpoints() {
+    let arc = Arc::from_endpoints(10f32, 50f32, 40f32, 40f32, 0f32, false, true, 90f32, 50f32).unwrap();
+    let (center_x, center_y) = arc.center();
+    assert_approx_eq!(50f32, center_x, 1e-3f32);
+    assert_approx_eq!(50f32, center_y, 1e-3f32);
+    assert_approx_eq!(PI, arc.sweep_angle(), 1e-3f32);
+}
+
+#[test]
+fn sweep_flag_selects_direction() {
+    let positive = Arc::from_endpoints(10f32, 50f32, 40f32, 40f32, 0f32, false, true, 90f32, 50f32).unwrap();
+    let negative = Arc::from_endpoints(10f32, 50f32, 40f32, 40f32, 0f32, false, false, 90f32, 50f32).unwrap();
+    assert!(positive.sweep_angle() > 0f32);
+    assert!(negative.sweep_angle() < 0f32);
+    // Positive angle direction goes through the top in SVG's y-down coordinates.
+    assert_approx_eq!(10f32, positive.point_at(0.5f32).1, 1e-3f32);
+    assert_approx_eq!(90f32, negative.point_at(0.5f32).1, 1e-3f32);
+}
+
+#[test]
+fn large_arc_flag_selects_longer_arc() {
+    let small = Arc::from_endpoints(0f32, 0f32, 10f32, 10f32, 0f32, false, true, 10f32, 10f32).unwrap();
+    let large = Arc::from_endpoints(0f32, 0f32, 10f32, 10f32, 0f32, true, true, 10f32, 10f32).unwrap();
+    assert_approx_eq!(PI / 2f32, small.sweep_angle(), 1e-3f32);
+    assert_approx_eq!(3f32 * PI / 2f32, large.sweep_angle(), 1e-3f32);
+    assert_ne!(small.center(), large.center());
+}
+
+#[test]
+fn too_small_radii_are_scaled_up() {
+    let arc = Arc::from_endpoints(0f32, 0f32, 1f32, 2f32, 0f32, false, true, 20f32, 0f32).unwrap();
+    let (radius_x, radius_y) = arc.radii();
+    assert_approx_eq!(10f32, radius_x, 1e-3f32);
+    assert_approx_eq!(20f32, radius_y, 1e-3f32);
+    assert_approx_eq!(10f32, arc.center().0, 1e-3f32);
+    assert_approx_eq!(0f32, arc.center().1, 1e-3f32);
+}
+
+#[test]
+fn endpoints_are_reproduced() {
+    let arc = Arc::from_endpoints(3f32, 7f32, 20f32, 10f32, 30f32, true, false, 25f32, -4f32).unwrap();
+    let (start_x, start_y) = arc.point_at(0f32);
+    let (end_x, end_y) = arc.point_at(1f32);
+    assert_approx_eq!(3f32, start_x, 1e-3f32);
+    assert_approx_eq!(7f32, start_y, 1e-3f32);
+    assert_approx_eq!(25f32, end_x, 1e-3f32);
+    assert_approx_eq!(-4f32, end_y, 1e-3f32);
+}
+
+#[test]
+fn rotation_by_90_degrees_swaps_radii() {
+    let rotated = Arc::from_endpoints(0f32, 0f32, 20f32, 10f32, 90f32, false, true, 0f32, 40f32).unwrap();
+    let plain = Arc::from_endpoints(0f32, 0f32, 10f32, 20f32, 0f32, false, true, 0f32, 40f32).unwrap();
+    for index in 0..10 {
+        let t = index as f32 / 10f32;
+        assert_approx_eq!(plain.point_at(t).0, rotated.point_at(t).0, 1e-3f32);
+        assert_approx_eq!(plain.point_at(t).1, rotated.point_at(t).1, 1e-3f32);
+    }
+}
+
+#[test]
+fn degenerate_arcs_are_lines() {
+    assert!(Arc::from_endpoints(0f32, 0f32, 10f32, 10f32, 0f32, false, true, 0f32, 0f32).is_none());
+    assert!(Arc::from_endpoints(0f32, 0f32, 0f32, 10f32, 0f32, false, true, 10f32, 0f32).is_none());
+}
@@ -0,0 +1,59 @@
+extern crate svg_bot;
+
+use std::time::Duration;
+
+use svg_bot::backend::PointerBackend;
+use svg_bot::batch_backend::BatchingXdotoolBackend;
+use svg_bot::error;
+
+// `true` accepts any arguments, so nothing touches the real pointer.
+fn backend(batch_size: usize) -> BatchingXdotoolBackend {
+    BatchingXdotoolBackend::with_program("true", batch_size)
+}
+
+#[test]
+fn commands_are_chained() {
+    let mut backend = backend(100);
+    backend.move_to(10f32, 20.5f32).unwrap();
+    backend.press().unwrap();
+    backend.pause(Duration::from_millis(250)).unwrap();
+    backend.move_to(30f32, 40f32).unwrap();
+    backend.release().unwrap();
+    assert_eq!(vec!["mousemove", "10", "20.5", "mousedown", "1", "sleep", "0.25",
+                    "mousemove", "30", "40", "mouseup", "1"],
+               backend.pending_args());
+}
+
+#[test]
+fn full_batch_is_sent() {
+    let mut backend = backend(2);
+    backend.move_to(1f32, 1f32).unwrap();
+    assert_eq!(3, backend.pending_args().len());
+    backend.move_to(2f32, 2f32).unwrap();
+    assert!(backend.pending_args().is_empty());
+    backend.press().unwrap();
+    assert_eq!(vec!["mousedown", "1"], backend.pending_args());
+}
+
+#[test]
+fn flush_sends_pending_commands() {
+    let mut backend = backend(100);
+    backend.press().unwrap();
+    backend.flush().unwrap();
+    assert!(backend.pending_args().is_empty());
+}
+
+#[test]
+fn failure_is_reported() {
+    let mut backend = BatchingXdotoolBackend::with_program("false", 100);
+    backend.press().unwrap();
+    assert!(backend.flush().is_err());
+}
+
+#[test]
+fn missing_program_is_reported() {
+    let mut backend = BatchingXdotoolBackend::with_program("svg-bot-missing-xdotool", 100);
+    backend.press().unwrap();
+    let error = backend.flush().unwrap_err();
+    assert!(error::chain(&error).starts_with("Couldn't run svg-bot-missing-xdotool: "), "{}", error::chain(&error));
+}
@@ -0,0 +1,139 @@
+extern crate svg_bot;
+
+use std::time::Duration;
+
+use svg_bot::cli::{self, BackendKind, Command};
+use svg_bot::executor;
+use svg_bot::fit::ScaleMode;
+use svg_bot::pacing::Pacing;
+use svg_bot::rect::Rect;
+
+fn parse(args: &[&str]) -> Result<Command, svg_bot::error::Error> {
+    cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn draw_defaults() {
+    match parse(&["draw", "image.svg"]).unwrap() {
+        Command::Draw(plan, run) => {
+            assert_eq!("image.svg", plan.svg);
+            assert_eq!(None, plan.area.area);
+            assert_eq!(ScaleMode::Auto, plan.scale_mode);
+            assert!(!plan.keep_order);
+            assert_eq!(Pacing::default(), run.pacing);
+            assert_eq!(BackendKind::XdotoolBatch, run.backend);
+            assert_eq!(Some(executor::DEFAULT_STOP_THRESHOLD), run.stop_threshold);
+            assert!(!run.resume);
+            assert!(!run.dry_run);
+        }
+        command => panic!("Unexpected {:?}", command),
+    }
+}
+
+#[test]
+fn draw_with_options() {
+    let command = parse(&["draw", "--area=10,20,300,200", "image.svg", "--backend", "xtest", "--speed", "paint",
+                          "--move-delay", "7", "--stop-threshold=0", "--keep-order", "--dry-run"]).unwrap();
+    match command {
+        Command::Draw(plan, run) => {
+            assert_eq!("image.svg", plan.svg);
+            assert_eq!(Some(Rect::new(10f32, 20f32, 300f32, 200f32)), plan.area.area);
+            assert!(plan.keep_order);
+            assert_eq!(BackendKind::XTest, run.backend);
+            assert_eq!(Duration::from_millis(7), run.pacing.move_delay());
+            assert_eq!(Pacing::preset("paint").unwrap().press_delay(), run.pacing.press_delay());
+            assert_eq!(None, run.stop_threshold);
+            assert!(run.dry_run);
+        }
+        command => panic!("Unexpected {:?}", command),
+    }
+}
+
+#[test]
+fn delays_override_the_preset_in_any_order() {
+    for args in &[["--speed", "paint", "--move-delay", "5"], ["--move-delay", "5", "--speed", "paint"]] {
+        match parse(&["record", "image.svg", args[0], args[1], args[2], args[3]]).unwrap() {
+            Command::Record(_, pacing) => {
+                assert_eq!(Pacing::preset("paint").unwrap().with_move_delay(Duration::from_millis(5)), pacing);
+            }
+            command => panic!("Unexpected {:?}", command),
+        }
+    }
+}
+
+#[test]
+fn calibrate() {
+    assert_eq!(Command::Calibrate{ name: "paint".to_string(), countdown: true, profiles_path: Some("p".to_string()) },
+               parse(&["calibrate", "--countdown", "paint", "--profiles", "p"]).unwrap());
+    assert!(parse(&["calibrate"]).is_err());
+    assert!(parse(&["calibrate", "two words"]).is_err());
+}
+
+#[test]
+fn output_options() {
+    match parse(&["preview", "-o", "out.svg", "image.svg"]).unwrap() {
+        Command::Preview(plan, output) => {
+            assert_eq!("image.svg", plan.svg);
+            assert_eq!(Some("out.svg".to_string()), output);
+        }
+        command => panic!("Unexpected {:?}", command),
+    }
+    match parse(&["export", "image.svg", "--output=plan.json"]).unwrap() {
+        Command::Export(_, output) => assert_eq!(Some("plan.json".to_string()), output),
+        command => panic!("Unexpected {:?}", command),
+    }
+}
+
+#[test]
+fn help() {
+    assert_eq!(Command::Help(None), parse(&["help"]).unwrap());
+    assert_eq!(Command::Help(None), parse(&["--help"]).unwrap());
+    assert_eq!(Command::Help(Some("draw".to_string())), parse(&["help", "draw"]).unwrap());
+    assert_eq!(Command::Help(Some("draw".to_string())), parse(&["draw", "--bogus", "-h"]).unwrap());
+}
+
+#[test]
+fn errors() {
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["paint", "image.svg"]).is_err());
+    assert!(parse(&["draw"]).is_err());
+    assert!(parse(&["draw", "image.svg", "--bogus"]).is_err());
+    assert!(parse(&["draw", "image.svg", "other.svg"]).is_err());
+    assert!(parse(&["draw", "image.svg", "--margin"]).is_err());
+    assert!(parse(&["draw", "image.svg", "--margin", "-1"]).is_err());
+    assert!(parse(&["draw", "image.svg", "--move-delay", "1.5"]).is_err());
+    // Options of other commands aren't accepted.
+    assert!(parse(&["info", "image.svg", "--output", "out"]).is_err());
+    assert!(parse(&["preview", "image.svg", "--resume"]).is_err());
+    // A dry run doesn't touch the mouse.
+    assert!(parse(&["draw", "image.svg", "--dry-run", "--countdown"]).is_err());
+}
+
+#[test]
+fn resume_keeps_the_journal_area() {
+    match parse(&["draw", "image.svg", "--resume"]).unwrap() {
+        Command::Draw(_, run) => assert!(run.resume),
+        command => panic!("Unexpected {:?}", command),
+    }
+    assert!(parse(&["draw", "image.svg", "--resume", "--area", "0,0,10,10"]).is_err());
+    assert!(parse(&["draw", "image.svg", "--profile", "paint", "--resume"]).is_err());
+}
+
+#[test]
+fn usage_of_every_command() {
+    assert!(cli::usage(None).unwrap().contains("Commands:"));
+    for command in &["draw", "calibrate", "preview", "info", "record", "export"] {
+        let usage = cli::usage(Some(command)).unwrap();
+        assert!(usage.contains(&format!("Usage: svg-bot {} ", command)), "{}", usage);
+    }
+    assert!(cli::usage(Some("draw")).unwrap().contains("--resume"));
+    assert!(cli::usage(Some("paint")).is_err());
+}
+
+#[test]
+fn backend_kind() {
+    assert_eq!(BackendKind::Xdotool, BackendKind::parse("xdotool").unwrap());
+    assert_eq!(BackendKind::XdotoolBatch, BackendKind::parse("xdotool-batch").unwrap());
+    assert_eq!(BackendKind::XTest, BackendKind::parse("xtest").unwrap());
+    assert!(BackendKind::parse("xdo").is_err());
+}
@@ -0,0 +1,190 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg;
+extern crate svg_bot;
+
+use std::error::Error as StdError;
+
+use svg_bot::rect::Rect;
+use svg_bot::error::Error;
+use svg_bot::document;
+use svg_bot::point::ScreenPoint;
+use svg_bot::stroke_plan::StrokePlan;
+use svg_bot::transform::Transform;
+
+fn compile(content: &str, svg_to_screen: &Transform) -> StrokePlan {
+    document::compile(svg::read(content.as_bytes()).unwrap(), svg_to_screen).unwrap()
+}
+
+fn draw(content: &str) -> Vec<Vec<(f32, f32)>> {
+    compile(content, &Transform::identity()).strokes().iter()
+        .map(|stroke| stroke.points().iter().map(|point| (point.x(), point.y())).collect())
+        .collect()
+}
+
+fn assert_stroke_eq(expected: &[(f32, f32)], actual: &[(f32, f32)]) {
+    assert_eq!(expected.len(), actual.len(), "expected {:?}, got {:?}", expected, actual);
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        assert_approx_eq!(expected.0, actual.0, 1e-3f32);
+        assert_approx_eq!(expected.1, actual.1, 1e-3f32);
+    }
+}
+
+#[test]
+fn every_drawable_element_is_drawn() {
+    let strokes = draw(r#"<svg viewBox="0 0 100 100">
+        <path d="M0 0 L10 0"/>
+        <line x1="0" y1="10" x2="10" y2="10"/>
+        <rect x="0" y="20" width="10" height="10"/>
+        <polyline points="0,40 10,40"></polyline>
+        <text x="0" y="50">Not drawn</text>
+    </svg>"#);
+    assert_eq!(4, strokes.len());
+    assert_stroke_eq(&[(0f32, 40f32), (10f32, 40f32)], &strokes[3]);
+}
+
+#[test]
+fn background_rect_covers_view_box() {
+    let strokes = draw(r#"<svg viewBox="0 0 200 100"><rect width="100%" height="100%" fill="white"/></svg>"#);
+    assert_stroke_eq(&[(0f32, 0f32), (200f32, 0f32), (200f32, 100f32), (0f32, 100f32), (0f32, 0f32)], &strokes[0]);
+}
+
+#[test]
+fn content_of_not_rendered_containers_isnt_drawn() {
+    let strokes = draw(r#"<svg viewBox="0 0 100 100">
+        <defs>
+            <clipPath id="clip"><rect width="100" height="100"/></clipPath>
+            <path id="referenced" d="M0 0 L10 0"/>
+        </defs>
+        <mask><circle r="5"/></mask>
+        <symbol><line x2="10"/></symbol>
+        <marker><path d="M0 0 L1 1"/></marker>
+        <pattern><rect width="1" height="1"/></pattern>
+        <path d="M0 50 L10 50" clip-path="url(#clip)"/>
+    </svg>"#);
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(0f32, 50f32), (10f32, 50f32)], &strokes[0]);
+}
+
+#[test]
+fn element_transform_is_applied() {
+    let strokes = draw(r#"<svg><path d="M0 0 L10 0" transform="translate(5 5)"/></svg>"#);
+    assert_stroke_eq(&[(5f32, 5f32), (15f32, 5f32)], &strokes[0]);
+}
+
+#[test]
+fn nested_group_transforms_are_combined() {
+    let strokes = draw(r#"<svg>
+        <g transform="translate(10 20)">
+            <g transform="scale(2)">
+                <path d="M0 0 L10 0" transform="rotate(90)"/>
+            </g>
+        </g>
+    </svg>"#);
+    assert_stroke_eq(&[(10f32, 20f32), (10f32, 40f32)], &strokes[0]);
+}
+
+#[test]
+fn group_transform_ends_with_group() {
+    let strokes = draw(r#"<svg>
+        <g transform="translate(10 20)">
+            <path d="M0 0 L10 0"/>
+        </g>
+        <path d="M0 0 L10 0"/>
+    </svg>"#);
+    assert_stroke_eq(&[(10f32, 20f32), (20f32, 20f32)], &strokes[0]);
+    assert_stroke_eq(&[(0f32, 0f32), (10f32, 0f32)], &strokes[1]);
+}
+
+#[test]
+fn transformed_curves_stay_within_tolerance() {
+    let strokes = draw(r#"<svg><g transform="scale(10)"><circle cx="5" cy="5" r="4"/></g></svg>"#);
+    for point in &strokes[0] {
+        let distance = ((point.0 - 50f32).powi(2) + (point.1 - 50f32).powi(2)).sqrt();
+        assert_approx_eq!(40f32, distance, 1e-2f32);
+    }
+    // Polyline of a radius 40 circle needs way more points to stay within half a pixel than of a radius 4 one.
+    assert!(strokes[0].len() > 20);
+}
+
+#[test]
+fn invalid_transform_is_an_error() {
+    let content = r#"<svg><path d="M0 0 L10 0" transform="spin(5)"/></svg>"#;
+    assert!(document::compile(svg::read(content.as_bytes()).unwrap(), &Transform::identity()).is_err());
+}
+
+#[test]
+fn path_data_error_tells_element_and_offset() {
+    let content = r#"<svg><path id="wave" d="M0 0 L10 0 C1 2 3 4"/></svg>"#;
+    match document::compile(svg::read(content.as_bytes()).unwrap(), &Transform::identity()) {
+        Err(Error::PathData{ element_id, offset, .. }) => {
+            assert_eq!(Some("wave".to_string()), element_id);
+            assert_eq!(Some(11), offset);
+        }
+        result => panic!("Expected path data error, got {:?}", result),
+    }
+}
+
+#[test]
+fn unparsable_path_data_tells_offset_and_cause() {
+    let content = r#"<svg><path d="M0 0 L10 0 X5"/></svg>"#;
+    match document::compile(svg::read(content.as_bytes()).unwrap(), &Transform::identity()) {
+        Err(error @ Error::PathData{ .. }) => {
+            match error {
+                Error::PathData{ element_id: None, offset: Some(11), .. } => {}
+                ref error => panic!("Unexpected {:?}", error),
+            }
+            assert!(StdError::source(&error).is_some());
+        }
+        result => panic!("Expected path data error, got {:?}", result),
+    }
+}
+
+#[test]
+fn view_box_origin_is_respected() {
+    let content = r#"<svg viewBox="50 50 100 100"><path d="M50 50 L150 150"/></svg>"#;
+    let svg_to_screen = Transform::rect_to_rect(&Rect::new(50f32, 50f32, 100f32, 100f32),
+                                                &Rect::new(10f32, 20f32, 200f32, 200f32));
+    let plan = compile(content, &svg_to_screen);
+    assert_eq!(ScreenPoint::new(10f32, 20f32), plan.strokes()[0].points()[0]);
+    assert_eq!(ScreenPoint::new(210f32, 220f32), plan.strokes()[0].points()[1]);
+}
+
+#[test]
+fn content_bounds_cover_everything_drawn() {
+    let content = r#"<svg width="100%">
+        <path d="M10 20 L30 20"/>
+        <g transform="translate(100 0)"><circle cx="0" cy="50" r="10"/></g>
+    </svg>"#;
+    let bounds = document::content_bounds(svg::read(content.as_bytes()).unwrap()).unwrap().unwrap();
+    assert_approx_eq!(10f32, bounds.x(), 1e-3f32);
+    assert_approx_eq!(20f32, bounds.y(), 1e-3f32);
+    assert_approx_eq!(100f32, bounds.width(), 1e-3f32);
+    assert_approx_eq!(40f32, bounds.height(), 1e-3f32);
+}
+
+#[test]
+fn document_without_content_has_no_bounds() {
+    let content = r#"<svg><text>Hello</text></svg>"#;
+    assert!(document::content_bounds(svg::read(content.as_bytes()).unwrap()).unwrap().is_none());
+}
+
+#[test]
+fn strokes_know_their_element_and_color() {
+    let plan = compile(r##"<svg>
+        <g stroke="red">
+            <path id="first" d="M0 0 L10 0 M20 0 L30 0"/>
+            <path d="M0 0 L10 0" style="fill: none; stroke: #00f"/>
+        </g>
+        <path id="last" d="M0 0 L10 0"/>
+    </svg>"##, &Transform::identity());
+    let strokes = plan.strokes();
+    assert_eq!(4, strokes.len());
+    assert_eq!(Some("first"), strokes[0].element_id());
+    assert_eq!(Some("first"), strokes[1].element_id());
+    assert_eq!(Some("red"), strokes[1].color());
+    assert_eq!(None, strokes[2].element_id());
+    assert_eq!(Some("#00f"), strokes[2].color());
+    assert_eq!(Some("last"), strokes[3].element_id());
+    assert_eq!(None, strokes[3].color());
+}
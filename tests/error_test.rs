@@ -0,0 +1,30 @@
+extern crate svg_bot;
+
+use std::error::Error as StdError;
+use std::io;
+
+use svg_bot::error::{self, Error};
+
+#[test]
+fn path_data_error_is_located() {
+    let error = Error::path_data("Line expects pairs of params, got 1".to_string()).at(Some("wave"), Some(7));
+    assert_eq!("Invalid path data of element 'wave' at offset 7: Line expects pairs of params, got 1", error.to_string());
+    // A located error keeps its place.
+    let error = error.at(Some("other"), Some(0));
+    assert!(error.to_string().contains("'wave' at offset 7"));
+}
+
+#[test]
+fn other_errors_are_not_located() {
+    let error = Error::Parse("Couldn't parse length 'wide'".to_string()).at(Some("wave"), Some(7));
+    assert_eq!("Couldn't parse length 'wide'", error.to_string());
+}
+
+#[test]
+fn sources_are_chained() {
+    let error = Error::io("Couldn't open a.svg".to_string(), io::Error::new(io::ErrorKind::NotFound, "missing"));
+    assert!(error.source().is_some());
+    assert_eq!("Couldn't open a.svg", error.to_string());
+    assert_eq!("Couldn't open a.svg: missing", error::chain(&error));
+    assert!(Error::backend("xdotool mousedown finished with failure".to_string()).source().is_none());
+}
@@ -0,0 +1,327 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use std::env;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use svg_bot::backend::PointerBackend;
+use svg_bot::executor::{Executor, Outcome};
+use svg_bot::interrupt::InterruptFlag;
+use svg_bot::journal::Journal;
+use svg_bot::error::Error;
+use svg_bot::pause::PauseSwitch;
+use svg_bot::rect::Rect;
+use svg_bot::pacing::{self, Pacing};
+use svg_bot::point::ScreenPoint;
+use svg_bot::recording_backend::{PointerEventKind, RecordingBackend};
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+
+fn plan(strokes: &[&[(f32, f32)]]) -> StrokePlan {
+    StrokePlan::new(strokes.iter().map(|points| Stroke::new(
+        points.iter().map(|&(x, y)| ScreenPoint::new(x, y)).collect(), None, None)).collect())
+}
+
+fn execute(plan: &StrokePlan, pacing: Pacing) -> RecordingBackend {
+    let mut backend = RecordingBackend::new();
+    {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_pacing(pacing);
+        executor.execute(plan).unwrap();
+    }
+    backend
+}
+
+#[test]
+fn strokes_are_drawn_with_pen_lifts_between_them() {
+    let backend = execute(&plan(&[&[(0f32, 0f32), (10f32, 0f32)], &[], &[(20f32, 0f32), (30f32, 5f32)]]),
+                          Pacing::default());
+    let kinds: Vec<PointerEventKind> = backend.events().iter().map(|event| event.kind().clone()).collect();
+    assert_eq!(vec![PointerEventKind::Move(0f32, 0f32), PointerEventKind::Press,
+                    PointerEventKind::Move(10f32, 0f32), PointerEventKind::Release,
+                    PointerEventKind::Move(20f32, 0f32), PointerEventKind::Press,
+                    PointerEventKind::Move(30f32, 5f32), PointerEventKind::Release], kinds);
+}
+
+#[test]
+fn long_segments_are_split_into_steps() {
+    let pacing = Pacing::default().with_max_step(Some(4f32));
+    let backend = execute(&plan(&[&[(0f32, 0f32), (10f32, 0f32), (10f32, 2f32)]]), pacing);
+    let strokes = backend.strokes();
+    let expected = [(0f32, 0f32), (3.333f32, 0f32), (6.667f32, 0f32), (10f32, 0f32), (10f32, 2f32)];
+    assert_eq!(expected.len(), strokes[0].len());
+    for (&(expected_x, expected_y), &(x, y)) in expected.iter().zip(&strokes[0]) {
+        assert_approx_eq!(expected_x, x, 1e-3f32);
+        assert_approx_eq!(expected_y, y, 1e-3f32);
+    }
+}
+
+#[test]
+fn pen_up_travel_isnt_split() {
+    let pacing = Pacing::default().with_max_step(Some(1f32));
+    let backend = execute(&plan(&[&[(0f32, 0f32)], &[(100f32, 0f32)]]), pacing);
+    assert_eq!(6, backend.events().len());
+}
+
+#[test]
+fn delays_are_respected() {
+    let pacing = Pacing::new(Duration::from_millis(10), Duration::from_millis(100),
+                             Duration::from_millis(1000), None);
+    let backend = execute(&plan(&[&[(0f32, 0f32), (10f32, 0f32)]]), pacing);
+    let times: Vec<Duration> = backend.events().iter().map(|event| event.time()).collect();
+    // move, press, move, release
+    assert!(times[1] - times[0] >= Duration::from_millis(110));
+    assert!(times[2] - times[1] >= Duration::from_millis(100));
+    assert!(times[3] - times[2] >= Duration::from_millis(1010));
+}
+
+#[test]
+fn presets() {
+    for name in pacing::PRESETS {
+        assert!(Pacing::preset(name).is_ok(), "{}", name);
+    }
+    assert_eq!(Pacing::default(), Pacing::preset("instant").unwrap());
+    assert!(Pacing::preset("paint").unwrap().max_step().is_some());
+    assert!(Pacing::preset("warp").is_err());
+}
+
+/// Pretends the user grabs the mouse and moves it away after a number of moves.
+struct GrabbedBackend {
+    recording: RecordingBackend,
+    moves_before_grab: usize,
+}
+
+impl PointerBackend for GrabbedBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.recording.press()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.recording.release()
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.moves_before_grab = self.moves_before_grab.saturating_sub(1);
+        self.recording.move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        let (x, y) = self.recording.position()?;
+        if self.moves_before_grab == 0 {
+            Ok((x + 100f32, y.round()))
+        } else {
+            Ok((x.round(), y.round()))
+        }
+    }
+}
+
+fn execute_grabbed(plan: &StrokePlan, moves_before_grab: usize, threshold: Option<f32>) -> (Outcome, GrabbedBackend) {
+    let mut backend = GrabbedBackend{ recording: RecordingBackend::new(), moves_before_grab };
+    let outcome = {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_stop_threshold(threshold);
+        executor.execute(plan).unwrap()
+    };
+    (outcome, backend)
+}
+
+#[test]
+fn stops_when_mouse_is_grabbed() {
+    let plan = plan(&[&[(0f32, 0f32), (10f32, 0.4f32)], &[(20f32, 0f32), (30f32, 0f32), (40f32, 0f32)]]);
+    let (outcome, backend) = execute_grabbed(&plan, 4, Some(20f32));
+    let stop = match outcome {
+        Outcome::Stopped(stop) => stop,
+        outcome => panic!("Drawing wasn't stopped: {:?}", outcome),
+    };
+    assert_eq!(1, stop.stroke());
+    assert_eq!(2, stop.point());
+    assert_eq!(&ScreenPoint::new(30f32, 0f32), stop.expected());
+    assert_eq!(&ScreenPoint::new(130f32, 0f32), stop.actual());
+    assert_eq!(vec![vec![(0f32, 0f32), (10f32, 0.4f32)], vec![(20f32, 0f32), (30f32, 0f32)]],
+               backend.recording.strokes());
+    assert_eq!(&PointerEventKind::Release, backend.recording.events().last().unwrap().kind());
+}
+
+#[test]
+fn rounded_positions_dont_stop_drawing() {
+    let plan = plan(&[&[(0.4f32, 0.4f32), (10.6f32, 0.5f32)]]);
+    let (outcome, _) = execute_grabbed(&plan, 100, Some(0.1f32));
+    assert_eq!(Outcome::Finished, outcome);
+}
+
+#[test]
+fn stop_can_be_disabled() {
+    let plan = plan(&[&[(0f32, 0f32), (10f32, 0f32), (20f32, 0f32)]]);
+    let (outcome, backend) = execute_grabbed(&plan, 1, None);
+    assert_eq!(Outcome::Finished, outcome);
+    assert_eq!(3, backend.recording.strokes()[0].len());
+}
+
+#[test]
+fn drawing_starts_at_first_stroke() {
+    let mut backend = RecordingBackend::new();
+    {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_first_stroke(1);
+        executor.execute(&plan(&[&[(0f32, 0f32), (1f32, 0f32)], &[(2f32, 0f32), (3f32, 0f32)]])).unwrap();
+    }
+    assert_eq!(vec![vec![(2f32, 0f32), (3f32, 0f32)]], backend.strokes());
+}
+
+#[test]
+fn completed_strokes_are_journaled() {
+    let path = env::temp_dir().join(format!("svg_bot_executor_test_{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let plan = plan(&[&[(0f32, 0f32), (1f32, 0f32)], &[(2f32, 0f32), (3f32, 0f32)], &[(4f32, 0f32)]]);
+    let journal = Journal::create(&path, plan.fingerprint(), Rect::new(0f32, 0f32, 10f32, 10f32)).unwrap();
+    let mut backend = GrabbedBackend{ recording: RecordingBackend::new(), moves_before_grab: 4 };
+    let outcome = {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_stop_threshold(Some(10f32));
+        executor.set_journal(journal);
+        executor.execute(&plan).unwrap()
+    };
+    match outcome {
+        Outcome::Stopped(stop) => assert_eq!(2, stop.stroke()),
+        outcome => panic!("Drawing wasn't stopped: {:?}", outcome),
+    }
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(2, journal.completed());
+    assert_eq!(plan.fingerprint(), journal.fingerprint());
+    journal.remove().unwrap();
+}
+
+#[test]
+fn paused_drawing_waits() {
+    let switch = PauseSwitch::new();
+    assert!(switch.toggle());
+    let resumer = {
+        let switch = switch.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            switch.set_paused(false);
+        })
+    };
+    let started = Instant::now();
+    let mut backend = RecordingBackend::new();
+    {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_pause_switch(switch.clone());
+        executor.execute(&plan(&[&[(0f32, 0f32), (1f32, 0f32)]])).unwrap();
+    }
+    resumer.join().unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert!(!switch.is_paused());
+    assert_eq!(1, backend.strokes().len());
+}
+
+/// Sets the interrupt flag after a number of moves, as a signal arriving mid-drawing would.
+struct InterruptedBackend {
+    recording: RecordingBackend,
+    moves_before_interrupt: usize,
+    flag: InterruptFlag,
+}
+
+impl PointerBackend for InterruptedBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.recording.press()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.recording.release()
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.moves_before_interrupt = self.moves_before_interrupt.saturating_sub(1);
+        if self.moves_before_interrupt == 0 {
+            self.flag.interrupt();
+        }
+        self.recording.move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        self.recording.position()
+    }
+}
+
+#[test]
+fn interrupted_drawing_lifts_the_pen() {
+    let plan = plan(&[&[(0f32, 0f32), (10f32, 0f32)], &[(20f32, 0f32), (30f32, 0f32), (40f32, 0f32)]]);
+    let flag = InterruptFlag::new();
+    let mut backend = InterruptedBackend{ recording: RecordingBackend::new(), moves_before_interrupt: 4, flag: flag.clone() };
+    let outcome = {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_interrupt_flag(flag);
+        executor.execute(&plan).unwrap()
+    };
+    assert_eq!(Outcome::Interrupted, outcome);
+    assert_eq!(vec![vec![(0f32, 0f32), (10f32, 0f32)], vec![(20f32, 0f32), (30f32, 0f32)]],
+               backend.recording.strokes());
+    assert_eq!(PointerEventKind::Release, *backend.recording.events().last().unwrap().kind());
+}
+
+#[test]
+fn interrupt_before_stroke_draws_nothing_more() {
+    let flag = InterruptFlag::new();
+    flag.interrupt();
+    let mut backend = RecordingBackend::new();
+    let outcome = {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_interrupt_flag(flag);
+        executor.execute(&plan(&[&[(0f32, 0f32), (10f32, 0f32)]])).unwrap()
+    };
+    assert_eq!(Outcome::Interrupted, outcome);
+    assert!(backend.events().is_empty());
+}
+
+/// Counts flushes, each of which spawns a process with the batching backend.
+struct FlushCountingBackend {
+    recording: RecordingBackend,
+    flushes: usize,
+}
+
+impl PointerBackend for FlushCountingBackend {
+    fn press(&mut self) -> Result<(), Error> {
+        self.recording.press()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.recording.release()
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.recording.move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        self.recording.position()
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn journal_saves_are_batched() {
+    let path = env::temp_dir().join(format!("svg_bot_executor_batched_test_{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let plan = plan(&[&[(0f32, 0f32), (1f32, 0f32)], &[(2f32, 0f32)], &[(3f32, 0f32)], &[(4f32, 0f32)], &[(5f32, 0f32)]]);
+    let journal = Journal::create(&path, plan.fingerprint(), Rect::new(0f32, 0f32, 10f32, 10f32)).unwrap();
+    let mut backend = FlushCountingBackend{ recording: RecordingBackend::new(), flushes: 0 };
+    let outcome = {
+        let mut executor = Executor::new(&mut backend);
+        executor.set_journal(journal);
+        executor.set_journal_interval(Duration::from_secs(3600));
+        executor.execute(&plan).unwrap()
+    };
+    assert_eq!(Outcome::Finished, outcome);
+    // After the first stroke, at the end of drawing and when the rest is saved.
+    assert_eq!(3, backend.flushes);
+    let journal = Journal::open(&path).unwrap();
+    assert_eq!(5, journal.completed());
+    journal.remove().unwrap();
+}
@@ -0,0 +1,110 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use svg_bot::rect::Rect;
+use svg_bot::transform::Transform;
+use svg_bot::fit::{svg_to_screen, Align, PreserveAspectRatio, ScaleMode};
+
+fn assert_maps(transform: &Transform, from: (f32, f32), to: (f32, f32)) {
+    let (x, y) = transform.apply(from.0, from.1);
+    assert_approx_eq!(to.0, x, 1e-3f32);
+    assert_approx_eq!(to.1, y, 1e-3f32);
+}
+
+// 100x50 viewBox starting at (10, 10) into a 200x200 area at (0, 0).
+fn fit(aspect_ratio: &str, scale_mode: ScaleMode, margin: f32) -> Transform {
+    let view_box = Rect::new(10f32, 10f32, 100f32, 50f32);
+    let paint_area = Rect::new(0f32, 0f32, 200f32, 200f32);
+    let aspect_ratio = PreserveAspectRatio::parse(aspect_ratio).unwrap();
+    svg_to_screen(&view_box, &paint_area, &aspect_ratio, &scale_mode, margin).unwrap()
+}
+
+#[test]
+fn aspect_ratio_is_parsed() {
+    assert_eq!(PreserveAspectRatio::new(Some((Align::Min, Align::Max)), true),
+               PreserveAspectRatio::parse("xMinYMax slice").unwrap());
+    assert_eq!(PreserveAspectRatio::new(Some((Align::Max, Align::Mid)), false),
+               PreserveAspectRatio::parse("defer xMaxYMid").unwrap());
+    assert_eq!(PreserveAspectRatio::new(None, false), PreserveAspectRatio::parse("none").unwrap());
+    assert_eq!(PreserveAspectRatio::default(), PreserveAspectRatio::parse("xMidYMid meet").unwrap());
+}
+
+#[test]
+fn invalid_aspect_ratio_is_an_error() {
+    assert!(PreserveAspectRatio::parse("").is_err());
+    assert!(PreserveAspectRatio::parse("xMidYMed").is_err());
+    assert!(PreserveAspectRatio::parse("xMidYMid cut").is_err());
+    assert!(PreserveAspectRatio::parse("xMidYMid meet please").is_err());
+}
+
+#[test]
+fn scale_mode_is_parsed() {
+    assert_eq!(ScaleMode::Fit, ScaleMode::parse("fit").unwrap());
+    assert_eq!(ScaleMode::Fill, ScaleMode::parse("fill").unwrap());
+    assert_eq!(ScaleMode::Stretch, ScaleMode::parse("stretch").unwrap());
+    assert_eq!(ScaleMode::Fixed(1.5f32), ScaleMode::parse("1.5").unwrap());
+    assert!(ScaleMode::parse("-1").is_err());
+    assert!(ScaleMode::parse("huge").is_err());
+}
+
+#[test]
+fn meet_centers_view_box() {
+    let transform = fit("xMidYMid meet", ScaleMode::Auto, 0f32);
+    assert_maps(&transform, (10f32, 10f32), (0f32, 50f32));
+    assert_maps(&transform, (110f32, 60f32), (200f32, 150f32));
+}
+
+#[test]
+fn meet_aligns_to_min_and_max() {
+    assert_maps(&fit("xMinYMin meet", ScaleMode::Auto, 0f32), (10f32, 10f32), (0f32, 0f32));
+    assert_maps(&fit("xMaxYMax meet", ScaleMode::Auto, 0f32), (110f32, 60f32), (200f32, 200f32));
+}
+
+#[test]
+fn slice_covers_area() {
+    let transform = fit("xMidYMid slice", ScaleMode::Auto, 0f32);
+    assert_maps(&transform, (10f32, 10f32), (-100f32, 0f32));
+    assert_maps(&transform, (110f32, 60f32), (300f32, 200f32));
+    assert_maps(&fit("xMinYMin slice", ScaleMode::Auto, 0f32), (10f32, 10f32), (0f32, 0f32));
+    assert_maps(&fit("xMaxYMin slice", ScaleMode::Auto, 0f32), (110f32, 60f32), (200f32, 200f32));
+}
+
+#[test]
+fn none_stretches() {
+    let transform = fit("none", ScaleMode::Auto, 0f32);
+    assert_maps(&transform, (10f32, 10f32), (0f32, 0f32));
+    assert_maps(&transform, (110f32, 60f32), (200f32, 200f32));
+}
+
+#[test]
+fn scale_mode_overrides_aspect_ratio() {
+    assert_maps(&fit("none", ScaleMode::Fit, 0f32), (10f32, 10f32), (0f32, 0f32));
+    assert_maps(&fit("none", ScaleMode::Fit, 0f32), (110f32, 60f32), (200f32, 100f32));
+    assert_maps(&fit("xMidYMid meet", ScaleMode::Fill, 0f32), (10f32, 10f32), (-100f32, 0f32));
+    assert_maps(&fit("xMidYMid meet", ScaleMode::Stretch, 0f32), (110f32, 60f32), (200f32, 200f32));
+}
+
+#[test]
+fn fixed_scale_keeps_alignment() {
+    let transform = fit("xMidYMid meet", ScaleMode::Fixed(1f32), 0f32);
+    assert_maps(&transform, (10f32, 10f32), (50f32, 75f32));
+    assert_maps(&transform, (110f32, 60f32), (150f32, 125f32));
+}
+
+#[test]
+fn margin_shrinks_area() {
+    let transform = fit("xMinYMin meet", ScaleMode::Auto, 20f32);
+    assert_maps(&transform, (10f32, 10f32), (20f32, 20f32));
+    assert_maps(&transform, (110f32, 60f32), (180f32, 100f32));
+}
+
+#[test]
+fn margin_leaving_no_room_is_an_error() {
+    let view_box = Rect::new(0f32, 0f32, 100f32, 50f32);
+    let paint_area = Rect::new(0f32, 0f32, 200f32, 100f32);
+    let aspect_ratio = PreserveAspectRatio::default();
+    assert!(svg_to_screen(&view_box, &paint_area, &aspect_ratio, &ScaleMode::Auto, 49f32).is_ok());
+    assert!(svg_to_screen(&view_box, &paint_area, &aspect_ratio, &ScaleMode::Auto, 50f32).is_err());
+    assert!(svg_to_screen(&view_box, &paint_area, &aspect_ratio, &ScaleMode::Fixed(1f32), 80f32).is_err());
+}
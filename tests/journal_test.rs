@@ -0,0 +1,54 @@
+extern crate svg_bot;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use svg_bot::journal::Journal;
+use svg_bot::rect::Rect;
+
+fn journal_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("svg_bot_journal_test_{}_{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn progress_survives_reopening() {
+    let path = journal_path("progress");
+    let mut journal = Journal::create(&path, 0xdead_beef, Rect::new(10f32, 20.5f32, 300f32, 400f32)).unwrap();
+    journal.set_completed(7).unwrap();
+
+    let reopened = Journal::open(&path).unwrap();
+    assert_eq!(0xdead_beef, reopened.fingerprint());
+    assert_eq!(&Rect::new(10f32, 20.5f32, 300f32, 400f32), reopened.paint_area());
+    assert_eq!(7, reopened.completed());
+
+    reopened.remove().unwrap();
+    assert!(!path.exists());
+}
+
+#[test]
+fn missing_journal_is_an_error() {
+    assert!(Journal::open(&journal_path("missing")).is_err());
+}
+
+#[test]
+fn corrupted_journal_is_an_error() {
+    let path = journal_path("corrupted");
+    fs::write(&path, "fingerprint 12\narea 1 2 3\ncompleted 4\n").unwrap();
+    assert!(Journal::open(&path).is_err());
+    fs::write(&path, "fingerprint 12\narea 1 2 3 4\n").unwrap();
+    assert!(Journal::open(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn existing_journal_isnt_overwritten() {
+    let path = journal_path("existing");
+    let mut journal = Journal::create(&path, 1, Rect::new(0f32, 0f32, 10f32, 10f32)).unwrap();
+    journal.set_completed(3).unwrap();
+    assert!(Journal::create(&path, 2, Rect::new(0f32, 0f32, 10f32, 10f32)).is_err());
+    assert_eq!(3, Journal::open(&path).unwrap().completed());
+    journal.remove().unwrap();
+}
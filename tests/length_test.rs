@@ -0,0 +1,47 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use svg_bot::length::{Length, Unit};
+
+fn px(value: &str) -> f32 {
+    Length::parse(value).unwrap().to_px(None).unwrap()
+}
+
+#[test]
+fn unitless_length_is_in_px() {
+    assert_eq!(Length::new(12.5f32, Unit::Px), Length::parse("12.5").unwrap());
+    assert_eq!(Length::new(12.5f32, Unit::Px), Length::parse(" 12.5px ").unwrap());
+}
+
+#[test]
+fn absolute_units_are_converted_to_px() {
+    assert_approx_eq!(96f32, px("1in"), 1e-3f32);
+    assert_approx_eq!(96f32, px("2.54cm"), 1e-3f32);
+    assert_approx_eq!(793.7008f32, px("210mm"), 1e-3f32);
+    assert_approx_eq!(96f32, px("72pt"), 1e-3f32);
+    assert_approx_eq!(96f32, px("6pc"), 1e-3f32);
+    assert_approx_eq!(32f32, px("2em"), 1e-3f32);
+}
+
+#[test]
+fn percentage_needs_a_base() {
+    let length = Length::parse("50%").unwrap();
+    assert_eq!(Unit::Percent, length.unit());
+    assert_eq!(None, length.to_px(None));
+    assert_approx_eq!(100f32, length.to_px(Some(200f32)).unwrap());
+}
+
+#[test]
+fn exponent_is_not_mistaken_for_unit() {
+    assert_approx_eq!(100f32, px("1e2"));
+    assert_approx_eq!(160f32, px("1e1em"));
+}
+
+#[test]
+fn invalid_lengths_are_errors() {
+    assert!(Length::parse("").is_err());
+    assert!(Length::parse("mm").is_err());
+    assert!(Length::parse("10 parsecs").is_err());
+    assert!(Length::parse("10km").is_err());
+}
@@ -0,0 +1,69 @@
+extern crate svg_bot;
+
+use svg_bot::merge::merge;
+use svg_bot::point::ScreenPoint;
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+
+fn stroke(points: &[(f32, f32)], element_id: Option<&str>, color: Option<&str>) -> Stroke {
+    Stroke::new(points.iter().map(|&(x, y)| ScreenPoint::new(x, y)).collect(),
+                element_id.map(String::from),
+                color.map(String::from))
+}
+
+fn points(stroke: &Stroke) -> Vec<(f32, f32)> {
+    stroke.points().iter().map(|point| (point.x(), point.y())).collect()
+}
+
+#[test]
+fn touching_strokes_are_joined() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)], Some("a"), None),
+        stroke(&[(10f32, 0f32), (10f32, 10f32)], Some("a"), None),
+    ]);
+    let merged = merge(&plan, 1f32);
+    assert_eq!(1, merged.len());
+    assert_eq!(vec![(0f32, 0f32), (10f32, 0f32), (10f32, 10f32)], points(&merged.strokes()[0]));
+    assert_eq!(Some("a"), merged.strokes()[0].element_id());
+}
+
+#[test]
+fn strokes_are_reversed_and_prepended() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(10f32, 0f32), (20f32, 0f32)], Some("a"), None),
+        stroke(&[(30f32, 0f32), (20.5f32, 0f32)], Some("b"), None),
+        stroke(&[(0f32, 0f32), (10f32, 0.5f32)], Some("a"), None),
+    ]);
+    let merged = merge(&plan, 1f32);
+    assert_eq!(1, merged.len());
+    assert_eq!(vec![(0f32, 0f32), (10f32, 0.5f32), (10f32, 0f32), (20f32, 0f32), (20.5f32, 0f32), (30f32, 0f32)],
+               points(&merged.strokes()[0]));
+    assert_eq!(None, merged.strokes()[0].element_id());
+}
+
+#[test]
+fn distant_strokes_are_kept_apart() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)], None, None),
+        stroke(&[(12f32, 0f32), (20f32, 0f32)], None, None),
+    ]);
+    assert_eq!(2, merge(&plan, 1f32).len());
+    assert_eq!(1, merge(&plan, 2f32).len());
+}
+
+#[test]
+fn different_colors_are_kept_apart() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)], None, Some("red")),
+        stroke(&[(10f32, 0f32), (20f32, 0f32)], None, Some("blue")),
+    ]);
+    assert_eq!(2, merge(&plan, 1f32).len());
+}
+
+#[test]
+fn closed_strokes_are_not_extended() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32), (10f32, 10f32), (0f32, 0f32)], None, None),
+        stroke(&[(0f32, 0f32), (-10f32, 0f32)], None, None),
+    ]);
+    assert_eq!(2, merge(&plan, 1f32).len());
+}
@@ -0,0 +1,97 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use svg_bot::optimize::{self, travel_distance};
+use svg_bot::point::ScreenPoint;
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+
+fn stroke(points: &[(f32, f32)]) -> Stroke {
+    Stroke::new(points.iter().map(|&(x, y)| ScreenPoint::new(x, y)).collect(), None, None)
+}
+
+fn endpoints(plan: &StrokePlan) -> Vec<((f32, f32), (f32, f32))> {
+    plan.strokes().iter().map(|stroke| {
+        let points = stroke.points();
+        let last = &points[points.len() - 1];
+        ((points[0].x(), points[0].y()), (last.x(), last.y()))
+    }).collect()
+}
+
+#[test]
+fn travel_distance_sums_pen_up_moves() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)]),
+        stroke(&[]),
+        stroke(&[(10f32, 10f32), (20f32, 10f32)]),
+        stroke(&[(23f32, 14f32)]),
+    ]);
+    assert_approx_eq!(15f32, travel_distance(&plan));
+}
+
+#[test]
+fn strokes_are_reordered() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)]),
+        stroke(&[(100f32, 0f32), (110f32, 0f32)]),
+        stroke(&[(20f32, 0f32), (30f32, 0f32)]),
+    ]);
+    let (optimized, report) = optimize::optimize(&plan);
+    assert_eq!(vec![((0f32, 0f32), (10f32, 0f32)),
+                    ((20f32, 0f32), (30f32, 0f32)),
+                    ((100f32, 0f32), (110f32, 0f32))], endpoints(&optimized));
+    assert_approx_eq!(90f32 + 90f32, report.travel_before());
+    assert_approx_eq!(10f32 + 70f32, report.travel_after());
+    assert_approx_eq!(100f32, report.saved());
+}
+
+#[test]
+fn strokes_are_reversed() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)]),
+        stroke(&[(50f32, 0f32), (11f32, 0f32)]),
+    ]);
+    let (optimized, report) = optimize::optimize(&plan);
+    assert_eq!(vec![((0f32, 0f32), (10f32, 0f32)),
+                    ((11f32, 0f32), (50f32, 0f32))], endpoints(&optimized));
+    assert_approx_eq!(1f32, report.travel_after());
+}
+
+#[test]
+fn two_opt_fixes_crossing_travels() {
+    // Nearest neighbour goes right first and then has to come all the way back.
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (0f32, 1f32)]),
+        stroke(&[(2f32, 1f32), (2f32, 2f32)]),
+        stroke(&[(-3f32, 1f32), (-3f32, 2f32)]),
+        stroke(&[(5f32, 2f32), (5f32, 3f32)]),
+        stroke(&[(-7f32, 2f32), (-7f32, 3f32)]),
+    ]);
+    let (optimized, report) = optimize::optimize(&plan);
+    assert_eq!(5, optimized.len());
+    assert!(report.travel_after() <= report.travel_before());
+    assert_approx_eq!(travel_distance(&optimized), report.travel_after());
+    // Going left first and then right once is the best tour.
+    assert!(report.travel_after() < 25f32, "travel {}", report.travel_after());
+}
+
+#[test]
+fn no_strokes_are_lost() {
+    let plan = StrokePlan::new((0..20).map(|i| {
+        let x = ((i * 7) % 20) as f32 * 10f32;
+        stroke(&[(x, 0f32), (x + 5f32, 5f32), (x, 10f32)])
+    }).collect());
+    let (optimized, report) = optimize::optimize(&plan);
+    assert_eq!(20, optimized.len());
+    let mut starts: Vec<i32> = optimized.strokes().iter().map(|stroke| stroke.points()[0].x() as i32).collect();
+    starts.sort();
+    assert_eq!((0..20).map(|i| i * 10).collect::<Vec<i32>>(), starts);
+    assert!(report.saved() > 0f32);
+}
+
+#[test]
+fn empty_plan() {
+    let (optimized, report) = optimize::optimize(&StrokePlan::default());
+    assert!(optimized.is_empty());
+    assert_approx_eq!(0f32, report.saved());
+}
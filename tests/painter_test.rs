@@ -0,0 +1,326 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg;
+extern crate svg_bot;
+
+use svg::node::element::path::Data;
+use svg_bot::rect::Rect;
+use svg_bot::error::Error;
+use svg_bot::painter::Painter;
+use svg_bot::transform::Transform;
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+use svg_bot::executor::Executor;
+use svg_bot::recording_backend::{RecordingBackend, PointerEventKind};
+
+// Screen area is twice as big as the SVG and shifted by (20, 20),
+// so SVG's (x, y) ends up at (x*2 + 20, y*2 + 20).
+fn draw(path_data: &str) -> RecordingBackend {
+    let svg_area = Rect::new(0f32, 0f32, 100f32, 100f32);
+    let screen_area = Rect::new(20f32, 20f32, 200f32, 200f32);
+    let mut painter = Painter::new(Transform::rect_to_rect(&svg_area, &screen_area));
+    for command in Data::parse(path_data).unwrap().iter() {
+        painter.perform_command(command).unwrap();
+    }
+    execute(painter)
+}
+
+fn execute(painter: Painter) -> RecordingBackend {
+    let strokes = painter.into_polylines().into_iter().map(|polyline| Stroke::new(polyline, None, None)).collect();
+    let mut backend = RecordingBackend::new();
+    Executor::new(&mut backend).execute(&StrokePlan::new(strokes)).unwrap();
+    backend
+}
+
+fn assert_stroke_eq(expected: &[(f32, f32)], actual: &[(f32, f32)]) {
+    assert_eq!(expected.len(), actual.len(), "expected {:?}, got {:?}", expected, actual);
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        assert_approx_eq!(expected.0, actual.0, 1e-3f32);
+        assert_approx_eq!(expected.1, actual.1, 1e-3f32);
+    }
+}
+
+fn distance_to_polyline(point: (f32, f32), polyline: &[(f32, f32)]) -> f32 {
+    polyline.windows(2).map(|segment| {
+        let (start, end) = (segment[0], segment[1]);
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let length_squared = dx * dx + dy * dy;
+        let t = if length_squared > 0f32 {
+            (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0f32, 1f32)
+        } else {
+            0f32
+        };
+        ((point.0 - start.0 - t * dx).powi(2) + (point.1 - start.1 - t * dy).powi(2)).sqrt()
+    }).fold(f32::MAX, f32::min)
+}
+
+// Checks that the stroke stays within the default half-pixel tolerance from the curve given in screen coords.
+fn assert_follows_curve<F>(stroke: &[(f32, f32)], curve: F) where F: Fn(f32) -> (f32, f32) {
+    for index in 0..1001 {
+        let point = curve(index as f32 / 1000f32);
+        let distance = distance_to_polyline(point, stroke);
+        assert!(distance <= 0.5f32 + 1e-3f32, "{:?} is {} px away from {:?}", point, distance, stroke);
+    }
+}
+
+#[test]
+fn absolute_line_is_drawn_in_screen_coords() {
+    let strokes = draw("M10 10 L20 10 20 20").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32), (60f32, 60f32)], &strokes[0]);
+}
+
+#[test]
+fn relative_line_is_drawn_in_screen_coords() {
+    let strokes = draw("M10 10 l10 0 0 10").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32), (60f32, 60f32)], &strokes[0]);
+}
+
+#[test]
+fn move_with_extra_params_draws_implicit_line() {
+    let strokes = draw("M10 10 20 10 20 20").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32), (60f32, 60f32)], &strokes[0]);
+}
+
+#[test]
+fn move_lifts_the_pen() {
+    let strokes = draw("M10 10 L20 10 M30 30 L40 30").strokes();
+    assert_eq!(2, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32)], &strokes[0]);
+    assert_stroke_eq(&[(80f32, 80f32), (100f32, 80f32)], &strokes[1]);
+}
+
+#[test]
+fn relative_move_is_relative_to_current_point() {
+    let strokes = draw("M10 10 L20 10 m10 10 l10 0").strokes();
+    assert_eq!(2, strokes.len());
+    assert_stroke_eq(&[(80f32, 60f32), (100f32, 60f32)], &strokes[1]);
+}
+
+#[test]
+fn horizontal_and_vertical_lines_are_drawn() {
+    let strokes = draw("M10 10 H20 V20 h-10 v-10").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(
+        &[(40f32, 40f32), (60f32, 40f32), (60f32, 60f32), (40f32, 60f32), (40f32, 40f32)],
+        &strokes[0]);
+}
+
+#[test]
+fn close_returns_to_subpath_start() {
+    let strokes = draw("M10 10 L20 10 L20 20 Z").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32), (60f32, 60f32), (40f32, 40f32)], &strokes[0]);
+}
+
+#[test]
+fn close_after_relative_move_returns_to_moved_point() {
+    let strokes = draw("M10 10 L20 10 m0 10 l10 0 l0 10 z").strokes();
+    assert_eq!(2, strokes.len());
+    assert_stroke_eq(&[(60f32, 60f32), (80f32, 60f32), (80f32, 80f32), (60f32, 60f32)], &strokes[1]);
+}
+
+#[test]
+fn cubic_curve_is_flattened_from_start_to_end() {
+    let strokes = draw("M0 0 C0 10 10 10 10 0").strokes();
+    assert_eq!(1, strokes.len());
+    let stroke = &strokes[0];
+    assert_stroke_eq(&[(20f32, 20f32)], &stroke[..1]);
+    assert_stroke_eq(&[(40f32, 20f32)], &stroke[stroke.len() - 1..]);
+    assert_follows_curve(stroke, |t| {
+        let x = 3f32*(1f32-t) * t.powi(2) * 10f32 + t.powi(3) * 10f32;
+        let y = 3f32*(1f32-t).powi(2) * t * 10f32 + 3f32*(1f32-t) * t.powi(2) * 10f32;
+        (x * 2f32 + 20f32, y * 2f32 + 20f32)
+    });
+}
+
+#[test]
+fn relative_cubic_curve_is_relative_to_current_point() {
+    let absolute = draw("M10 10 C10 20 20 20 20 10").strokes();
+    let relative = draw("M10 10 c0 10 10 10 10 0").strokes();
+    assert_eq!(1, relative.len());
+    assert_stroke_eq(&absolute[0], &relative[0]);
+}
+
+#[test]
+fn pen_is_released_before_move() {
+    let backend = draw("M10 10 L20 10 M30 30");
+    let kinds = backend.events().iter().map(|event| event.kind().clone()).collect::<Vec<_>>();
+    let last_release = kinds.iter().rposition(|kind| *kind == PointerEventKind::Release).unwrap();
+    let last_press = kinds.iter().rposition(|kind| *kind == PointerEventKind::Press).unwrap();
+    assert!(last_release > last_press);
+}
+
+#[test]
+fn recorded_events_are_ordered_in_time() {
+    let backend = draw("M0 0 C0 10 10 10 10 0 L20 20 Z");
+    let events = backend.events();
+    assert!(!events.is_empty());
+    for pair in events.windows(2) {
+        assert!(pair[0].time() <= pair[1].time());
+    }
+}
+
+fn assert_on_circle(center: (f32, f32), radius: f32, stroke: &[(f32, f32)]) {
+    for point in stroke {
+        let distance = ((point.0 - center.0).powi(2) + (point.1 - center.1).powi(2)).sqrt();
+        assert_approx_eq!(radius, distance, 1e-2f32);
+    }
+}
+
+#[test]
+fn elliptical_arc_is_drawn_along_circle() {
+    let strokes = draw("M10 50 A40 40 0 0 1 90 50").strokes();
+    assert_eq!(1, strokes.len());
+    let stroke = &strokes[0];
+    assert!(stroke.len() > 2);
+    assert_stroke_eq(&[(40f32, 120f32)], &stroke[..1]);
+    assert_stroke_eq(&[(200f32, 120f32)], &stroke[stroke.len() - 1..]);
+    assert_on_circle((120f32, 120f32), 80f32, stroke);
+    assert!(stroke.iter().all(|point| point.1 <= 120f32 + 1e-3f32));
+}
+
+#[test]
+fn relative_arcs_can_be_chained() {
+    let strokes = draw("M10 50 a40 40 0 0 1 80 0 40 40 0 0 1 -80 0").strokes();
+    assert_eq!(1, strokes.len());
+    let stroke = &strokes[0];
+    assert_stroke_eq(&[(40f32, 120f32)], &stroke[stroke.len() - 1..]);
+    assert_on_circle((120f32, 120f32), 80f32, stroke);
+    assert!(stroke.iter().any(|point| point.1 > 190f32));
+    assert!(stroke.iter().any(|point| point.1 < 50f32));
+}
+
+#[test]
+fn arc_with_zero_radius_is_a_line() {
+    let strokes = draw("M10 10 A0 10 0 0 1 20 10").strokes();
+    assert_eq!(1, strokes.len());
+    assert_stroke_eq(&[(40f32, 40f32), (60f32, 40f32)], &strokes[0]);
+}
+
+#[test]
+fn arc_with_wrong_params_count_is_an_error() {
+    let svg_area = Rect::new(0f32, 0f32, 100f32, 100f32);
+    let mut painter = Painter::new(Transform::rect_to_rect(&svg_area, &svg_area));
+    let move_command = Data::parse("M10 10").unwrap();
+    painter.perform_command(&move_command[0]).unwrap();
+    let arc = Data::parse("A10 10 0 0 1 20").unwrap();
+    assert!(painter.perform_command(&arc[0]).is_err());
+}
+
+#[test]
+fn wrong_params_count_of_lines_is_an_error() {
+    for path_data in &["M10", "M10 10 20", "M10 10 L20", "M10 10 L", "M10 10 H", "M10 10 V"] {
+        let mut painter = Painter::new(Transform::identity());
+        let result = Data::parse(path_data).unwrap().iter()
+            .map(|command| painter.perform_command(command))
+            .collect::<Result<Vec<()>, Error>>();
+        match result {
+            Err(Error::PathData{ .. }) => {}
+            result => panic!("{}: expected path data error, got {:?}", path_data, result),
+        }
+    }
+}
+
+#[test]
+fn quadratic_curve_is_flattened_from_start_to_end() {
+    let strokes = draw("M0 0 Q5 10 10 0").strokes();
+    assert_eq!(1, strokes.len());
+    let stroke = &strokes[0];
+    assert_stroke_eq(&[(20f32, 20f32)], &stroke[..1]);
+    assert_stroke_eq(&[(40f32, 20f32)], &stroke[stroke.len() - 1..]);
+    assert_follows_curve(stroke, |t| {
+        let x = 2f32*(1f32-t) * t * 5f32 + t.powi(2) * 10f32;
+        let y = 2f32*(1f32-t) * t * 10f32;
+        (x * 2f32 + 20f32, y * 2f32 + 20f32)
+    });
+}
+
+#[test]
+fn quadratic_curve_can_have_several_segments() {
+    let absolute = draw("M0 0 Q5 10 10 0 15 -10 20 0").strokes();
+    let relative = draw("M0 0 q5 10 10 0 5 -10 10 0").strokes();
+    assert_eq!(1, absolute.len());
+    assert_stroke_eq(&[(60f32, 20f32)], &absolute[0][absolute[0].len() - 1..]);
+    assert!(absolute[0].iter().any(|point| point.1 > 25f32));
+    assert!(absolute[0].iter().any(|point| point.1 < 15f32));
+    assert_stroke_eq(&absolute[0], &relative[0]);
+}
+
+#[test]
+fn smooth_quadratic_curve_reflects_previous_control_point() {
+    let smooth = draw("M0 0 Q5 10 10 0 T20 0 30 0").strokes();
+    let explicit = draw("M0 0 Q5 10 10 0 Q15 -10 20 0 Q25 10 30 0").strokes();
+    assert_stroke_eq(&explicit[0], &smooth[0]);
+}
+
+#[test]
+fn relative_smooth_quadratic_curve_reflects_previous_control_point() {
+    let smooth = draw("M0 0 q5 10 10 0 t10 0").strokes();
+    let explicit = draw("M0 0 Q5 10 10 0 Q15 -10 20 0").strokes();
+    assert_stroke_eq(&explicit[0], &smooth[0]);
+}
+
+#[test]
+fn smooth_quadratic_curve_without_previous_quadratic_is_straight() {
+    let strokes = draw("M0 0 Q5 10 10 0 L20 0 T30 0").strokes();
+    assert_eq!(1, strokes.len());
+    let stroke = &strokes[0];
+    let line_start = stroke.iter().position(|point| point.0 >= 60f32).unwrap();
+    assert!(stroke[line_start..].iter().all(|point| (point.1 - 20f32).abs() < 1e-3f32));
+    assert_stroke_eq(&[(80f32, 20f32)], &stroke[stroke.len() - 1..]);
+}
+
+#[test]
+fn cubic_curve_can_have_several_segments() {
+    let absolute = draw("M0 0 C0 10 10 10 10 0 10 -10 20 -10 20 0").strokes();
+    let relative = draw("M0 0 c0 10 10 10 10 0 0 -10 10 -10 10 0").strokes();
+    assert_eq!(1, absolute.len());
+    assert_stroke_eq(&[(60f32, 20f32)], &absolute[0][absolute[0].len() - 1..]);
+    assert!(absolute[0].iter().any(|point| point.1 > 25f32));
+    assert!(absolute[0].iter().any(|point| point.1 < 15f32));
+    assert_stroke_eq(&absolute[0], &relative[0]);
+}
+
+#[test]
+fn smooth_cubic_curve_reflects_previous_second_control_point() {
+    let smooth = draw("M0 0 C0 10 10 10 10 0 S20 -10 20 0 30 10 30 0").strokes();
+    let explicit = draw("M0 0 C0 10 10 10 10 0 C10 -10 20 -10 20 0 C20 10 30 10 30 0").strokes();
+    assert_stroke_eq(&explicit[0], &smooth[0]);
+}
+
+#[test]
+fn relative_smooth_cubic_curve_reflects_previous_second_control_point() {
+    let smooth = draw("M0 0 c0 10 10 10 10 0 s10 -10 10 0").strokes();
+    let explicit = draw("M0 0 C0 10 10 10 10 0 C10 -10 20 -10 20 0").strokes();
+    assert_stroke_eq(&explicit[0], &smooth[0]);
+}
+
+#[test]
+fn smooth_cubic_curve_after_non_cubic_uses_current_point() {
+    let smooth = draw("M0 0 Q5 10 10 0 S20 -10 20 0").strokes();
+    let explicit = draw("M0 0 Q5 10 10 0 C10 0 20 -10 20 0").strokes();
+    assert_stroke_eq(&explicit[0], &smooth[0]);
+}
+
+#[test]
+fn bigger_curves_are_flattened_into_more_points() {
+    let small = draw("M0 0 C0 1 1 1 1 0").strokes();
+    let big = draw("M0 0 C0 100 100 100 100 0").strokes();
+    assert!(small[0].len() < big[0].len());
+}
+
+#[test]
+fn lower_tolerance_gives_more_points() {
+    let draw_with_tolerance = |tolerance: f32| {
+        let svg_area = Rect::new(0f32, 0f32, 100f32, 100f32);
+        let mut painter = Painter::new(Transform::rect_to_rect(&svg_area, &svg_area));
+        painter.set_tolerance(tolerance);
+        for command in Data::parse("M0 0 A50 50 0 0 1 100 0").unwrap().iter() {
+            painter.perform_command(command).unwrap();
+        }
+        execute(painter).strokes()
+    };
+    assert!(draw_with_tolerance(2f32)[0].len() < draw_with_tolerance(0.1f32)[0].len());
+}
@@ -0,0 +1,73 @@
+extern crate svg_bot;
+
+use std::cell::RefCell;
+use std::panic;
+
+use svg_bot::backend::PointerBackend;
+use svg_bot::error::Error;
+use svg_bot::pen_guard::PenGuard;
+use svg_bot::recording_backend::{PointerEventKind, RecordingBackend};
+
+/// Shares its events so that they can be checked after the guard owning the backend is gone.
+struct SharedBackend<'a> {
+    events: &'a RefCell<RecordingBackend>,
+}
+
+impl<'a> PointerBackend for SharedBackend<'a> {
+    fn press(&mut self) -> Result<(), Error> {
+        self.events.borrow_mut().press()
+    }
+
+    fn release(&mut self) -> Result<(), Error> {
+        self.events.borrow_mut().release()
+    }
+
+    fn move_to(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.events.borrow_mut().move_to(x, y)
+    }
+
+    fn position(&mut self) -> Result<(f32, f32), Error> {
+        self.events.borrow_mut().position()
+    }
+}
+
+fn kinds(backend: &RecordingBackend) -> Vec<PointerEventKind> {
+    backend.events().iter().map(|event| event.kind().clone()).collect()
+}
+
+#[test]
+fn pressed_pen_is_released_on_drop() {
+    let recording = RefCell::new(RecordingBackend::new());
+    {
+        let mut guard = PenGuard::new(SharedBackend{ events: &recording });
+        guard.press().unwrap();
+        guard.move_to(1f32, 1f32).unwrap();
+        assert!(guard.is_pressed());
+    }
+    assert_eq!(vec![PointerEventKind::Press, PointerEventKind::Move(1f32, 1f32), PointerEventKind::Release],
+               kinds(&recording.borrow()));
+}
+
+#[test]
+fn released_pen_isnt_released_again() {
+    let recording = RefCell::new(RecordingBackend::new());
+    {
+        let mut guard = PenGuard::new(SharedBackend{ events: &recording });
+        guard.press().unwrap();
+        guard.release().unwrap();
+        assert!(!guard.is_pressed());
+    }
+    assert_eq!(vec![PointerEventKind::Press, PointerEventKind::Release], kinds(&recording.borrow()));
+}
+
+#[test]
+fn pen_is_released_on_panic() {
+    let recording = RefCell::new(RecordingBackend::new());
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let mut guard = PenGuard::new(SharedBackend{ events: &recording });
+        guard.press().unwrap();
+        panic!("Something went wrong in the middle of a stroke");
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec![PointerEventKind::Press, PointerEventKind::Release], kinds(&recording.borrow()));
+}
@@ -5,111 +5,97 @@ extern crate svg_bot;
 use svg_bot::rect::Rect;
 use svg_bot::point::SvgPoint;
 use svg_bot::point::ScreenPoint;
+use svg_bot::transform::Transform;
+
+fn svg_to_screen() -> Transform {
+    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
+    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
+    Transform::rect_to_rect(&svg_area, &screen_area)
+}
 
 #[test]
 fn svg_point_constructs_correctly() {
     let x = 1f32;
     let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
 
-    let point = SvgPoint::new(x, y, &svg_area, &screen_area);
+    let point = SvgPoint::new(x, y);
 
     assert_eq!(x, point.x());
     assert_eq!(y, point.y());
-    assert_eq!(svg_area, *point.svg_area());
-    assert_eq!(screen_area, *point.screen_area());
 }
 
 #[test]
 fn screen_point_constructs_correctly() {
     let x = 1f32;
     let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
 
-    let point = ScreenPoint::new(x, y, &svg_area, &screen_area);
+    let point = ScreenPoint::new(x, y);
 
     assert_eq!(x, point.x());
     assert_eq!(y, point.y());
-    assert_eq!(svg_area, *point.svg_area());
-    assert_eq!(screen_area, *point.screen_area());
 }
 
 #[test]
 fn svg_point_constructed_from_screen_point_has_different_coords() {
-    let x = 1f32;
-    let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
-
-    let svg_point = ScreenPoint::new(x, y, &svg_area, &screen_area);
-    let screen_point = SvgPoint::from(svg_point.clone());
+    let screen_point = ScreenPoint::new(1f32, 3f32);
+    let svg_point = screen_point.to_svg(&svg_to_screen()).unwrap();
 
-    assert_ne!(svg_point.x(), screen_point.x());
-    assert_ne!(svg_point.y(), screen_point.y());
+    assert_ne!(screen_point.x(), svg_point.x());
+    assert_ne!(screen_point.y(), svg_point.y());
 }
 
 #[test]
 fn screen_point_constructed_from_svg_point_has_different_coords() {
-    let x = 1f32;
-    let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
-
-    let svg_point = SvgPoint::new(x, y, &svg_area, &screen_area);
-    let screen_point = ScreenPoint::from(svg_point.clone());
+    let svg_point = SvgPoint::new(1f32, 3f32);
+    let screen_point = svg_point.to_screen(&svg_to_screen());
 
     assert_ne!(svg_point.x(), screen_point.x());
     assert_ne!(svg_point.y(), screen_point.y());
 }
 
 #[test]
-fn svg_point_constructed_from_screen_point_has_same_areas() {
-    let x = 1f32;
-    let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
+fn view_box_origin_maps_to_screen_area_origin() {
+    let screen_point = SvgPoint::new(2f32, 4f32).to_screen(&svg_to_screen());
 
-    let svg_point = ScreenPoint::new(x, y, &svg_area, &screen_area);
-    let screen_point = SvgPoint::from(svg_point.clone());
-
-    assert_eq!(*svg_point.svg_area(), *screen_point.svg_area());
-    assert_eq!(*svg_point.screen_area(), *screen_point.screen_area());
+    assert_approx_eq!(6f32, screen_point.x());
+    assert_approx_eq!(5f32, screen_point.y());
 }
 
 #[test]
-fn screen_point_constructed_from_svg_point_has_same_areas() {
-    let x = 1f32;
-    let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
+fn view_box_corner_maps_to_screen_area_corner() {
+    let screen_point = SvgPoint::new(7f32, 10f32).to_screen(&svg_to_screen());
+
+    assert_approx_eq!(10f32, screen_point.x());
+    assert_approx_eq!(8f32, screen_point.y());
+}
 
-    let svg_point = SvgPoint::new(x, y, &svg_area, &screen_area);
-    let screen_point = ScreenPoint::from(svg_point.clone());
+#[test]
+fn screen_area_origin_maps_back_to_view_box_origin() {
+    let svg_point = ScreenPoint::new(6f32, 5f32).to_svg(&svg_to_screen()).unwrap();
 
-    assert_eq!(*svg_point.svg_area(), *screen_point.svg_area());
-    assert_eq!(*svg_point.screen_area(), *screen_point.screen_area());
+    assert_approx_eq!(2f32, svg_point.x());
+    assert_approx_eq!(4f32, svg_point.y());
 }
 
 #[test]
 fn points_recalculation_works() {
-    let x = 1f32;
-    let y = 3f32;
-    let svg_area = Rect::new(2f32, 4f32, 5f32, 6f32);
-    let screen_area = Rect::new(6f32, 5f32, 4f32, 3f32);
-
-    let svg_point = SvgPoint::new(x, y, &svg_area, &screen_area);
-    let screen_point = ScreenPoint::from(svg_point.clone());
-    let recalculated_svg_point = SvgPoint::from(screen_point.clone());
+    let svg_point = SvgPoint::new(1f32, 3f32);
+    let screen_point = svg_point.to_screen(&svg_to_screen());
+    let recalculated_svg_point = screen_point.to_svg(&svg_to_screen()).unwrap();
 
     assert_approx_eq!(svg_point.x(), recalculated_svg_point.x());
     assert_approx_eq!(svg_point.y(), recalculated_svg_point.y());
 }
 
+#[test]
+fn screen_point_cant_be_mapped_back_with_collapsing_transform() {
+    let point = ScreenPoint::new(1f32, 3f32);
+    assert!(point.to_svg(&Transform::scale(0f32, 0f32)).is_none());
+}
+
 #[test]
 fn can_offset_svg_point() {
-    let point = SvgPoint::new(1f32, 3f32, &Rect::new(2f32, 4f32, 5f32, 6f32), &Rect::new(6f32, 5f32, 4f32, 3f32));
+    let point = SvgPoint::new(1f32, 3f32);
 
     let offset_x = 123f32;
     let offset_y = 321f32;
@@ -120,11 +106,11 @@ fn can_offset_svg_point() {
 
 #[test]
 fn can_offset_screen_point() {
-    let point = ScreenPoint::new(1f32, 3f32, &Rect::new(2f32, 4f32, 5f32, 6f32), &Rect::new(6f32, 5f32, 4f32, 3f32));
+    let point = ScreenPoint::new(1f32, 3f32);
 
     let offset_x = 123f32;
     let offset_y = 321f32;
     let moved_point = point.clone().offset(offset_x, offset_y);
     assert_approx_eq!(moved_point.x(), point.x() + offset_x);
     assert_approx_eq!(moved_point.y(), point.y() + offset_y);
-}
\ No newline at end of file
+}
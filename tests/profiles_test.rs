@@ -0,0 +1,58 @@
+extern crate svg_bot;
+
+use std::env;
+use std::fs;
+
+use svg_bot::profiles::{self, parse_area, Profiles};
+use svg_bot::rect::Rect;
+
+#[test]
+fn profiles_are_saved_and_loaded() {
+    let path = env::temp_dir()
+        .join(format!("svg_bot_profiles_test_{}", std::process::id()))
+        .join("profiles");
+    let mut profiles = Profiles::new();
+    profiles.set("paint", Rect::new(10f32, 20f32, 300.5f32, 400f32)).unwrap();
+    profiles.set("browser", Rect::new(0f32, 0f32, 100f32, 100f32)).unwrap();
+    profiles.save(&path).unwrap();
+
+    let loaded = Profiles::load(&path).unwrap();
+    assert_eq!(profiles, loaded);
+    assert_eq!(Some(&Rect::new(10f32, 20f32, 300.5f32, 400f32)), loaded.get("paint"));
+    assert_eq!(vec!["browser", "paint"], loaded.names());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn missing_file_has_no_profiles() {
+    let path = env::temp_dir().join("svg_bot_profiles_test_missing");
+    assert_eq!(Profiles::new(), Profiles::load(&path).unwrap());
+}
+
+#[test]
+fn comments_and_garbage() {
+    let path = env::temp_dir().join(format!("svg_bot_profiles_test_garbage_{}", std::process::id()));
+    fs::write(&path, "# name x y width height\n\nscreen 0 0 1920 1080\n").unwrap();
+    assert_eq!(Some(&Rect::new(0f32, 0f32, 1920f32, 1080f32)), Profiles::load(&path).unwrap().get("screen"));
+    fs::write(&path, "screen 0 0 1920\n").unwrap();
+    assert!(Profiles::load(&path).is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn invalid_names_are_rejected() {
+    let mut profiles = Profiles::new();
+    assert!(profiles.set("", Rect::new(0f32, 0f32, 1f32, 1f32)).is_err());
+    assert!(profiles.set("two words", Rect::new(0f32, 0f32, 1f32, 1f32)).is_err());
+    assert!(profiles::validate_name("#comment").is_err());
+    assert!(profiles::validate_name("paint").is_ok());
+}
+
+#[test]
+fn areas_are_parsed() {
+    assert_eq!(Rect::new(10f32, 20f32, 30f32, 40.5f32), parse_area("10,20,30,40.5").unwrap());
+    assert_eq!(Rect::new(-10f32, 0f32, 30f32, 40f32), parse_area(" -10, 0, 30, 40").unwrap());
+    assert!(parse_area("10,20,30").is_err());
+    assert!(parse_area("10,20,0,40").is_err());
+    assert!(parse_area("a,b,c,d").is_err());
+}
@@ -0,0 +1,141 @@
+extern crate svg;
+extern crate svg_bot;
+
+use svg::node::Attributes;
+use svg::node::Value;
+use svg_bot::rect::Rect;
+use svg_bot::shapes::{element_data, parse_points};
+
+fn attributes(pairs: &[(&str, &str)]) -> Attributes {
+    let mut attributes = Attributes::new();
+    for &(name, value) in pairs {
+        attributes.insert(name.to_string(), Value::from(value));
+    }
+    attributes
+}
+
+fn commands(name: &str, pairs: &[(&str, &str)]) -> Option<String> {
+    element_data(name, &attributes(pairs), None).unwrap().map(|data| {
+        data.iter().map(|command| String::from(command.clone())).collect::<Vec<_>>().join(" ")
+    })
+}
+
+#[test]
+fn path_data_is_parsed() {
+    assert_eq!(Some("M1,2 L3,4".to_string()), commands("path", &[("d", "M1 2 L3 4")]));
+}
+
+#[test]
+fn path_without_data_draws_nothing() {
+    assert_eq!(None, commands("path", &[]));
+}
+
+#[test]
+fn rect_becomes_closed_path() {
+    assert_eq!(Some("M10,20 H40 V60 H10 z".to_string()),
+               commands("rect", &[("x", "10"), ("y", "20"), ("width", "30"), ("height", "40")]));
+}
+
+#[test]
+fn rect_without_size_draws_nothing() {
+    assert_eq!(None, commands("rect", &[("x", "10"), ("y", "20"), ("width", "30")]));
+}
+
+#[test]
+fn rounded_rect_has_arc_corners() {
+    assert_eq!(Some("M15,20 H35 A5,8,0,0,1,40,28 V52 A5,8,0,0,1,35,60 H15 A5,8,0,0,1,10,52 V28 A5,8,0,0,1,15,20 z".to_string()),
+               commands("rect", &[("x", "10"), ("y", "20"), ("width", "30"), ("height", "40"), ("rx", "5"), ("ry", "8")]));
+}
+
+#[test]
+fn rect_missing_radius_takes_the_other_one() {
+    assert_eq!(commands("rect", &[("width", "30"), ("height", "40"), ("rx", "5"), ("ry", "5")]),
+               commands("rect", &[("width", "30"), ("height", "40"), ("ry", "5")]));
+}
+
+#[test]
+fn rect_radii_are_clamped_to_half_of_size() {
+    assert_eq!(commands("rect", &[("width", "30"), ("height", "40"), ("rx", "15"), ("ry", "20")]),
+               commands("rect", &[("width", "30"), ("height", "40"), ("rx", "100"), ("ry", "100")]));
+}
+
+#[test]
+fn lengths_may_have_units() {
+    assert_eq!(Some("M0,0 H96 V48 H0 z".to_string()), commands("rect", &[("width", "1in"), ("height", "36pt")]));
+}
+
+#[test]
+fn percentages_are_relative_to_viewport() {
+    let viewport = Rect::new(0f32, 0f32, 200f32, 100f32);
+    let commands = |name: &str, pairs: &[(&str, &str)]| element_data(name, &attributes(pairs), Some(&viewport))
+        .unwrap().map(|data| data.iter().map(|command| String::from(command.clone())).collect::<Vec<_>>().join(" "));
+    assert_eq!(Some("M20,50 H220 V150 H20 z".to_string()),
+               commands("rect", &[("x", "10%"), ("y", "50%"), ("width", "100%"), ("height", "100%")]));
+    // The radius is relative to the normalized diagonal, sqrt((200² + 100²) / 2).
+    assert_eq!(Some("M15.811388,0 A15.811388,15.811388,0,0,1,-15.811388,0 A15.811388,15.811388,0,0,1,15.811388,0 z".to_string()),
+               commands("circle", &[("r", "10%")]));
+}
+
+#[test]
+fn percentages_without_viewport_draw_nothing() {
+    assert_eq!(None, commands("rect", &[("width", "100%"), ("height", "100%")]));
+}
+
+#[test]
+fn circle_becomes_two_arcs() {
+    assert_eq!(Some("M15,20 A5,5,0,0,1,5,20 A5,5,0,0,1,15,20 z".to_string()),
+               commands("circle", &[("cx", "10"), ("cy", "20"), ("r", "5")]));
+}
+
+#[test]
+fn ellipse_becomes_two_arcs() {
+    assert_eq!(Some("M15,20 A5,3,0,0,1,5,20 A5,3,0,0,1,15,20 z".to_string()),
+               commands("ellipse", &[("cx", "10"), ("cy", "20"), ("rx", "5"), ("ry", "3")]));
+}
+
+#[test]
+fn circle_without_radius_draws_nothing() {
+    assert_eq!(None, commands("circle", &[("cx", "10"), ("cy", "20")]));
+}
+
+#[test]
+fn line_becomes_move_and_line() {
+    assert_eq!(Some("M1,2 L3,4".to_string()),
+               commands("line", &[("x1", "1"), ("y1", "2"), ("x2", "3"), ("y2", "4")]));
+}
+
+#[test]
+fn polyline_is_open_and_polygon_is_closed() {
+    assert_eq!(Some("M1,2 L3,4,5,6".to_string()), commands("polyline", &[("points", "1,2 3,4 5,6")]));
+    assert_eq!(Some("M1,2 L3,4,5,6 z".to_string()), commands("polygon", &[("points", "1,2 3,4 5,6")]));
+}
+
+#[test]
+fn points_accept_mixed_separators() {
+    assert_eq!(vec![1f32, 2f32, 3f32, 4f32, 5.5f32, -6f32], parse_points(" 1 ,2\n3,4\t5.5 -6 ").unwrap());
+}
+
+#[test]
+fn points_accept_compact_form() {
+    assert_eq!(vec![0f32, 0f32, 10f32, -5f32, 20f32, -10f32], parse_points("0,0 10-5 20-10").unwrap());
+    assert_eq!(vec![10f32, -5f32], parse_points("10-5").unwrap());
+    assert_eq!(vec![1e2f32, -3f32], parse_points("1e+2-3").unwrap());
+}
+
+#[test]
+fn odd_coordinate_is_dropped() {
+    assert_eq!(vec![1f32, 2f32], parse_points("1,2 3").unwrap());
+}
+
+#[test]
+fn invalid_points_are_an_error() {
+    let error = parse_points("1,2 a,4").unwrap_err();
+    assert!(error.to_string().contains("coordinate 'a'"), "{}", error);
+    assert!(element_data("rect", &attributes(&[("width", "wide"), ("height", "1")]), None).is_err());
+}
+
+#[test]
+fn non_drawable_elements_are_skipped() {
+    assert_eq!(None, commands("g", &[("transform", "scale(2)")]));
+    assert_eq!(None, commands("text", &[("x", "1")]));
+}
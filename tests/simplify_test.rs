@@ -0,0 +1,60 @@
+extern crate svg_bot;
+
+use svg_bot::point::ScreenPoint;
+use svg_bot::simplify::{simplify, simplify_points};
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+
+fn screen_points(points: &[(f32, f32)]) -> Vec<ScreenPoint> {
+    points.iter().map(|&(x, y)| ScreenPoint::new(x, y)).collect()
+}
+
+fn coords(points: &[ScreenPoint]) -> Vec<(f32, f32)> {
+    points.iter().map(|point| (point.x(), point.y())).collect()
+}
+
+#[test]
+fn collinear_points_are_removed() {
+    let points = screen_points(&[(0f32, 0f32), (10f32, 0.2f32), (20f32, -0.2f32), (30f32, 0f32)]);
+    assert_eq!(vec![(0f32, 0f32), (30f32, 0f32)], coords(&simplify_points(&points, 0.5f32)));
+}
+
+#[test]
+fn corners_are_kept() {
+    let points = screen_points(&[(0f32, 0f32), (10f32, 0f32), (20f32, 0f32), (20f32, 10f32), (20f32, 20f32)]);
+    assert_eq!(vec![(0f32, 0f32), (20f32, 0f32), (20f32, 20f32)], coords(&simplify_points(&points, 0.5f32)));
+}
+
+#[test]
+fn epsilon_is_respected() {
+    let points = screen_points(&[(0f32, 0f32), (10f32, 2f32), (20f32, 0f32)]);
+    assert_eq!(3, simplify_points(&points, 1f32).len());
+    assert_eq!(2, simplify_points(&points, 3f32).len());
+}
+
+#[test]
+fn points_of_the_same_pixel_are_dropped() {
+    let points = screen_points(&[(0f32, 0f32), (0.3f32, 0.1f32), (5f32, 5f32), (5.2f32, 4.9f32)]);
+    assert_eq!(vec![(0f32, 0f32), (5.2f32, 4.9f32)], coords(&simplify_points(&points, 0f32)));
+}
+
+#[test]
+fn tiny_stroke_becomes_a_dot() {
+    let points = screen_points(&[(1f32, 1f32), (1.2f32, 1.1f32)]);
+    assert_eq!(vec![(1f32, 1f32)], coords(&simplify_points(&points, 0.5f32)));
+}
+
+#[test]
+fn closed_stroke_keeps_its_shape() {
+    let points = screen_points(&[(0f32, 0f32), (10f32, 0f32), (10f32, 10f32), (0f32, 10f32), (0f32, 0f32)]);
+    assert_eq!(5, simplify_points(&points, 0.5f32).len());
+}
+
+#[test]
+fn plan_keeps_stroke_metadata() {
+    let plan = StrokePlan::new(vec![Stroke::new(screen_points(&[(0f32, 0f32), (1f32, 0f32), (2f32, 0f32)]),
+                                                Some("a".to_string()), Some("red".to_string()))]);
+    let simplified = simplify(&plan, 0.5f32);
+    assert_eq!(2, simplified.strokes()[0].points().len());
+    assert_eq!(Some("a"), simplified.strokes()[0].element_id());
+    assert_eq!(Some("red"), simplified.strokes()[0].color());
+}
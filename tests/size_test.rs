@@ -0,0 +1,55 @@
+extern crate svg;
+extern crate svg_bot;
+
+use svg::node::{Attributes, Value};
+use svg_bot::rect::Rect;
+use svg_bot::size::{parse_view_box, root_area};
+
+fn attributes(pairs: &[(&str, &str)]) -> Attributes {
+    let mut attributes = Attributes::new();
+    for &(name, value) in pairs {
+        attributes.insert(name.to_string(), Value::from(value));
+    }
+    attributes
+}
+
+#[test]
+fn view_box_accepts_commas_and_mixed_whitespace() {
+    let expected = Rect::new(-1f32, 2.5f32, 30f32, 40f32);
+    assert_eq!(expected, parse_view_box("-1 2.5 30 40").unwrap());
+    assert_eq!(expected, parse_view_box("-1,2.5,30,40").unwrap());
+    assert_eq!(expected, parse_view_box(" -1,\t2.5\n 30 ,40 ").unwrap());
+}
+
+#[test]
+fn invalid_view_boxes_are_errors() {
+    assert!(parse_view_box("0 0 30").is_err());
+    assert!(parse_view_box("0 0 30 40 50").is_err());
+    assert!(parse_view_box("0 0 thirty 40").is_err());
+    assert!(parse_view_box("0 0 0 40").is_err());
+    assert!(parse_view_box("0 0 30 -40").is_err());
+}
+
+#[test]
+fn view_box_has_priority_over_size() {
+    let area = root_area(&attributes(&[("viewBox", "1 2 3 4"), ("width", "100"), ("height", "200")])).unwrap();
+    assert_eq!(Some(Rect::new(1f32, 2f32, 3f32, 4f32)), area);
+}
+
+#[test]
+fn size_with_units_is_converted_to_px() {
+    let area = root_area(&attributes(&[("width", "1in"), ("height", "48px")])).unwrap();
+    assert_eq!(Some(Rect::new(0f32, 0f32, 96f32, 48f32)), area);
+}
+
+#[test]
+fn percentage_or_missing_size_is_unknown() {
+    assert_eq!(None, root_area(&attributes(&[("width", "100%"), ("height", "100%")])).unwrap());
+    assert_eq!(None, root_area(&attributes(&[("width", "100")])).unwrap());
+    assert_eq!(None, root_area(&attributes(&[])).unwrap());
+}
+
+#[test]
+fn invalid_size_is_an_error() {
+    assert!(root_area(&attributes(&[("width", "wide"), ("height", "100")])).is_err());
+}
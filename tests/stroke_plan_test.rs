@@ -0,0 +1,62 @@
+extern crate svg_bot;
+
+use svg_bot::point::ScreenPoint;
+use svg_bot::rect::Rect;
+use svg_bot::stroke_plan::{Stroke, StrokePlan};
+
+fn stroke(points: &[(f32, f32)], element_id: Option<&str>, color: Option<&str>) -> Stroke {
+    Stroke::new(points.iter().map(|&(x, y)| ScreenPoint::new(x, y)).collect(),
+                element_id.map(String::from),
+                color.map(String::from))
+}
+
+#[test]
+fn empty_plan_to_json() {
+    assert_eq!(r#"{"strokes":[]}"#, StrokePlan::default().to_json());
+}
+
+#[test]
+fn travels_are_between_strokes() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)], Some("a"), Some("red")),
+        stroke(&[], None, None),
+        stroke(&[(20f32, 5.5f32), (30f32, 5.5f32)], None, None),
+    ]);
+    assert_eq!(concat!(
+        r#"{"strokes":["#,
+        r#"{"pen":"down","element_id":"a","color":"red","points":[[0,0],[10,0]]},"#,
+        r#"{"pen":"up","points":[[10,0],[20,5.5]]},"#,
+        r#"{"pen":"down","element_id":null,"color":null,"points":[[20,5.5],[30,5.5]]}"#,
+        r#"]}"#), plan.to_json());
+}
+
+#[test]
+fn strings_are_escaped() {
+    let plan = StrokePlan::new(vec![stroke(&[(0f32, 0f32)], Some("a\"b\\c\n"), None)]);
+    assert!(plan.to_json().contains(r#""element_id":"a\"b\\c\n""#));
+}
+
+#[test]
+fn fingerprint_depends_on_points_only() {
+    let plan = StrokePlan::new(vec![stroke(&[(0f32, 0f32), (10f32, 0f32)], Some("a"), None)]);
+    let same = StrokePlan::new(vec![stroke(&[(0f32, 0f32), (10f32, 0f32)], Some("b"), Some("red"))]);
+    let moved = StrokePlan::new(vec![stroke(&[(0f32, 0f32), (10f32, 1f32)], Some("a"), None)]);
+    let split = StrokePlan::new(vec![stroke(&[(0f32, 0f32)], Some("a"), None),
+                                     stroke(&[(10f32, 0f32)], Some("a"), None)]);
+    assert_eq!(plan.fingerprint(), same.fingerprint());
+    assert!(plan.fingerprint() != moved.fingerprint());
+    assert!(plan.fingerprint() != split.fingerprint());
+}
+
+#[test]
+fn to_svg_draws_travels_dashed() {
+    let plan = StrokePlan::new(vec![
+        stroke(&[(0f32, 0f32), (10f32, 0f32)], None, None),
+        stroke(&[(20f32, 5f32)], None, None),
+    ]);
+    let svg = plan.to_svg(&Rect::new(0f32, 0f32, 40f32, 30f32));
+    assert!(svg.contains(r#"viewBox="0 0 40 30""#));
+    assert!(svg.contains(r#"<polyline stroke="black" points="0,0 10,0"/>"#));
+    assert!(svg.contains(r#"<line x1="10" y1="0" x2="20" y2="5" stroke="red""#));
+    assert!(svg.contains(r#"points="20,5 20,5""#));
+}
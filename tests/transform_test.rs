@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate assert_approx_eq;
+extern crate svg_bot;
+
+use svg_bot::transform::Transform;
+
+fn assert_maps(transform: &Transform, from: (f32, f32), to: (f32, f32)) {
+    let (x, y) = transform.apply(from.0, from.1);
+    assert_approx_eq!(to.0, x, 1e-4f32);
+    assert_approx_eq!(to.1, y, 1e-4f32);
+}
+
+#[test]
+fn identity_keeps_points() {
+    assert_maps(&Transform::identity(), (3f32, -4f32), (3f32, -4f32));
+}
+
+#[test]
+fn translate_and_scale_are_parsed() {
+    assert_maps(&Transform::parse("translate(10, 20)").unwrap(), (1f32, 2f32), (11f32, 22f32));
+    assert_maps(&Transform::parse("translate(10)").unwrap(), (1f32, 2f32), (11f32, 2f32));
+    assert_maps(&Transform::parse("scale(2 3)").unwrap(), (1f32, 2f32), (2f32, 6f32));
+    assert_maps(&Transform::parse("scale(2)").unwrap(), (1f32, 2f32), (2f32, 4f32));
+}
+
+#[test]
+fn matrix_is_parsed() {
+    assert_maps(&Transform::parse("matrix(1 2 3 4 5 6)").unwrap(), (1f32, 1f32), (9f32, 12f32));
+}
+
+#[test]
+fn rotate_is_parsed() {
+    assert_maps(&Transform::parse("rotate(90)").unwrap(), (1f32, 0f32), (0f32, 1f32));
+    assert_maps(&Transform::parse("rotate(90 10 10)").unwrap(), (11f32, 10f32), (10f32, 11f32));
+}
+
+#[test]
+fn skews_are_parsed() {
+    assert_maps(&Transform::parse("skewX(45)").unwrap(), (0f32, 2f32), (2f32, 2f32));
+    assert_maps(&Transform::parse("skewY(45)").unwrap(), (2f32, 0f32), (2f32, 2f32));
+}
+
+#[test]
+fn transform_list_is_applied_right_to_left() {
+    let transform = Transform::parse("translate(10,0) scale(2)").unwrap();
+    assert_maps(&transform, (1f32, 1f32), (12f32, 2f32));
+    let transform = Transform::parse("scale(2),translate(10,0)").unwrap();
+    assert_maps(&transform, (1f32, 1f32), (22f32, 2f32));
+}
+
+#[test]
+fn compact_numbers_are_parsed() {
+    assert_maps(&Transform::parse("translate(10-5)").unwrap(), (0f32, 0f32), (10f32, -5f32));
+    assert_maps(&Transform::parse("scale(1e1)").unwrap(), (1f32, 1f32), (10f32, 10f32));
+}
+
+#[test]
+fn invalid_transforms_are_errors() {
+    assert!(Transform::parse("translate(1, 2").is_err());
+    assert!(Transform::parse("rotate(1, 2)").is_err());
+    assert!(Transform::parse("wobble(1)").is_err());
+    assert!(Transform::parse("scale(a)").is_err());
+}
+
+#[test]
+fn inverse_undoes_transform() {
+    let transform = Transform::parse("translate(3 4) rotate(30) scale(2 5) skewX(10)").unwrap();
+    let inverse = transform.inverse().unwrap();
+    let (x, y) = transform.apply(7f32, -2f32);
+    assert_maps(&inverse, (x, y), (7f32, -2f32));
+}
+
+#[test]
+fn collapsing_transform_has_no_inverse() {
+    assert!(Transform::scale(0f32, 1f32).inverse().is_none());
+}
@@ -0,0 +1,36 @@
+extern crate svg_bot;
+
+use svg_bot::backend::PointerBackend;
+use svg_bot::xtest_backend::XTestBackend;
+
+// Tests which need an X server are ignored by default, run them against Xvfb with e.g.
+// `xvfb-run cargo test --test xtest_backend_test -- --ignored`
+
+#[test]
+fn missing_display_is_an_error() {
+    assert!(XTestBackend::connect(Some(":4242")).is_err());
+}
+
+#[test]
+#[ignore]
+fn moves_pointer() {
+    let mut backend = XTestBackend::new().unwrap();
+    backend.move_to(12f32, 34f32).unwrap();
+    backend.flush().unwrap();
+    assert_eq!((12f32, 34f32), backend.position().unwrap());
+    backend.move_to(56.4f32, 78.6f32).unwrap();
+    backend.flush().unwrap();
+    assert_eq!((56f32, 79f32), backend.position().unwrap());
+}
+
+#[test]
+#[ignore]
+fn presses_and_releases() {
+    let mut backend = XTestBackend::new().unwrap();
+    backend.move_to(10f32, 10f32).unwrap();
+    backend.press().unwrap();
+    backend.move_to(20f32, 20f32).unwrap();
+    backend.release().unwrap();
+    backend.flush().unwrap();
+    assert_eq!((20f32, 20f32), backend.position().unwrap());
+}